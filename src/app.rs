@@ -1,26 +1,75 @@
+pub mod collection;
+pub mod daily;
+pub mod history;
+pub mod save;
 pub mod states;
+pub mod stats;
+pub mod timer;
 
+use chrono::NaiveDate;
 use crossterm::event::{self, Event, KeyCode};
-use std::io::{self, Stdout};
+use std::{
+    io::{self, Stdout},
+    time::Duration,
+};
 use tui::{
     backend::{Backend, CrosstermBackend},
     layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
-    text::Spans,
-    widgets::{Block, BorderType, Borders, Paragraph},
+    text::{Span, Spans},
+    widgets::{Block, BorderType, Borders, Paragraph, Wrap},
     Frame,
 };
 
+use self::{
+    collection::{is_collection, Collection, Progress},
+    daily::{Daily, DAILY_DIFFICULTY, DAILY_FILE, DAILY_SAVE},
+    history::{History, Move},
+    save::{Save, SAVE_VERSION},
+    states::States,
+    stats::{Stats, STATS_FILE},
+    timer::{format_time, Timer},
+};
 use crate::{
-    board::{self, difficulties::Difficulties, Board},
+    board::{
+        self, difficulties::Difficulties, formats::Format, parse_error::ParseError, size::Size,
+        variant::Variant, Board,
+    },
+    solver::{rating::Rating, Solver, Step},
     term::Term,
 };
 
 /// The size of each tile
 const TILE_SIZE: u16 = 3;
 
-/// The size of the entire sudoku-board
-const SUDOKU_SIZE: u16 = TILE_SIZE * board::SIDE as u16;
+/// The size of each tile on boards of more than nine positions across, which are drawn without
+/// borders so the whole board still fits
+const SMALL_TILE_SIZE: u16 = 2;
+
+/// Gets the size of each tile
+///
+/// ## Arguments
+///
+/// * size - The size of the board shown
+fn tile_size(size: Size) -> u16 {
+    if size.side() > 9 {
+        SMALL_TILE_SIZE
+    } else {
+        TILE_SIZE
+    }
+}
+
+/// Gets the size of the entire sudoku-board
+///
+/// ## Arguments
+///
+/// * size - The size of the board shown
+fn sudoku_size(size: Size) -> u16 {
+    tile_size(size) * size.side() as u16
+}
+
+/// How long to wait for a key before drawing again, so the clock keeps ticking
+const TICK_RATE: Duration = Duration::from_millis(250);
 
 /// Function to get the string value from the specific part of the board
 ///
@@ -35,21 +84,78 @@ const SUDOKU_SIZE: u16 = TILE_SIZE * board::SIDE as u16;
 /// (the value, boolean)
 /// * The Value - is the value to get
 /// * Boolean - The boolean is true if the value is in the "Empty" board, and not just in the
-/// "tries" board
+///   "tries" board
 fn get_string_value(row: usize, col: usize, board: &Board) -> (String, bool) {
     // Is there a number in the "empty"-board?
     if let Some(val) = board.empty[row][col] {
-        ((val + 1).to_string(), true)
+        (board::symbol(val).to_string(), true)
     } else {
         // Is there a number in the board with tries?
         if let Some(val) = board[(row, col)] {
-            ((val + 1).to_string(), false)
+            (board::symbol(val).to_string(), false)
         } else {
             (String::from("_"), false)
         }
     }
 }
 
+/// Gets the file the game of a difficulty, size and variant is saved to
+///
+/// The classic board keeps the name it had before there were other sizes and variants
+fn save_name(difficulty: &Difficulties, size: Size, variant: Variant) -> String {
+    let mut name = format!("save-{}", difficulty);
+    if size != Size::default() {
+        name.push_str(&format!("-{}", size));
+    }
+    if variant != Variant::default() {
+        name.push_str(&format!("-{}", variant));
+    }
+    name
+}
+
+/// Gets the size and, unless it is the classic one, the variant of the new games, as shown in
+/// the menus
+fn game_name(app: &App) -> String {
+    if app.variant == Variant::default() {
+        app.size.to_string()
+    } else {
+        format!("{} {}", app.size, app.variant.name())
+    }
+}
+
+/// Tests if the contents of a file are a puzzle in the common one-line format, not a save
+///
+/// Saves are JSON, or from older versions a line with letters for the preset numbers
+fn is_puzzle(contents: &str) -> bool {
+    let line = contents.lines().next().unwrap_or_default();
+    !Save::is_save(contents) && !line.contains(|character: char| character.is_ascii_lowercase())
+}
+
+/// Gets the format to import a file in, so the file is never written over
+///
+/// The extension gives the format. A file without one of the extensions is a save, unless it is
+/// neither JSON nor the line format of older saves, then it is a puzzle in the common one-line
+/// format. A one-line puzzle with lowercase letters needs the .txt extension, as it can not be
+/// told apart from an older save
+///
+/// ## Returns
+///
+/// the format, None if the file is a save, or the error if the file can not be read
+fn import_format(file: &str) -> io::Result<Option<Format>> {
+    if let Some(format) = Format::from_path(file) {
+        return Ok(Some(format));
+    }
+    let contents = std::fs::read_to_string(file)?;
+    Ok(is_puzzle(&contents).then_some(Format::Standard))
+}
+
+/// Gets the name of a file without the folders and the extension
+fn file_stem(file: &str) -> String {
+    std::path::Path::new(file)
+        .file_stem()
+        .map_or(String::new(), |stem| stem.to_string_lossy().to_string())
+}
+
 pub struct App {
     /// The board to act upon
     board: Board,
@@ -59,28 +165,456 @@ pub struct App {
     active_row: isize,
     /// The file-name to save to
     file_name: String,
+    /// The steps leading up to the next value to place, the last step placing it
+    hint: Vec<Step>,
+    /// The position of an entry which is not in the solution, found instead of a hint
+    mistake: Option<(usize, usize)>,
+    /// If digits toggle notes instead of entering numbers
+    note_mode: bool,
+    /// The moves made, to undo and redo
+    history: History,
+    /// The time spent playing
+    timer: Timer,
+    /// The difficulty of the game, to keep the statistics for
+    difficulty: Difficulties,
+    /// The size of the new games
+    size: Size,
+    /// The variant of the new games
+    variant: Variant,
+    /// The seed the board was created from, None if it is not known
+    seed: Option<u64>,
+    /// The date of the daily puzzle played, None if it is not a daily puzzle
+    day: Option<NaiveDate>,
+    /// The times the daily puzzles were done in
+    daily: Daily,
+    /// The statistics of all games played
+    stats: Stats,
+    /// The screen shown
+    state: States,
+    /// The screen to go back to from the statistics
+    back: States,
+    /// The difficulty picked on the difficulty screens as its number, or the puzzle picked
+    selected: usize,
+    /// If there is a game which can be continued
+    in_game: bool,
+    /// If the game is already won, or was loaded won, so a win is only counted once
+    won: bool,
+    /// If the game won was won in the best time yet
+    new_best: bool,
+    /// The last error, shown until the next key is pressed
+    message: Option<String>,
+    /// The format the puzzle was imported from, to export it in
+    format: Option<Format>,
+    /// The comment lines of the file the puzzle was imported from
+    comments: Vec<String>,
+    /// The collection of puzzles given, to pick from
+    collection: Option<Collection>,
+    /// The index of the puzzle of the collection played, None if it is not from the collection
+    puzzle: Option<usize>,
 }
 
 impl App {
-    pub fn new(difficulty: Difficulties, file: Option<String>) -> Self {
+    /// Creates the app, which starts at the main menu unless a file is given to play
+    ///
+    /// ## Arguments
+    ///
+    /// * difficulty - The difficulty picked at first for a new game
+    /// * size - The size of the new games
+    /// * variant - The variant of the new games
+    /// * file - The file with a game to play
+    /// * seed - The seed of a new game to play, unless a file is given
+    /// * daily - If the daily puzzle should be played, unless a file is given
+    ///
+    /// ## Returns
+    ///
+    /// the app, or the error if the file given can not be read or is not a game
+    pub fn new(
+        difficulty: Difficulties,
+        size: Size,
+        variant: Variant,
+        file: Option<String>,
+        seed: Option<u64>,
+        daily: bool,
+    ) -> io::Result<Self> {
+        let mut app = Self {
+            board: Board::new_empty(size),
+            active_column: (size.side() / 2) as isize,
+            active_row: (size.side() / 2) as isize,
+            file_name: save_name(&difficulty, size, variant),
+            hint: vec![],
+            mistake: None,
+            note_mode: false,
+            history: History::default(),
+            timer: Timer::new(Duration::ZERO),
+            selected: difficulty.to_num() as usize,
+            difficulty,
+            size,
+            variant,
+            seed: None,
+            day: None,
+            daily: Daily::load(DAILY_FILE),
+            stats: Stats::load(STATS_FILE),
+            state: States::Menu,
+            back: States::Menu,
+            in_game: false,
+            won: false,
+            new_best: false,
+            message: None,
+            format: None,
+            comments: vec![],
+            collection: None,
+            puzzle: None,
+        };
         if let Some(file) = file {
-            let board = Board::from_string(
-                std::fs::read_to_string(&file).expect("That file does not exist here"),
-            );
-            Self {
-                board,
-                active_column: (board::SIDE / 2) as isize,
-                active_row: (board::SIDE / 2) as isize,
-                file_name: file,
+            if is_collection(&file) {
+                app.collection = Some(Collection::load(&file)?);
+                app.state = States::Puzzles;
+            } else {
+                if let Some(format) = import_format(&file)? {
+                    app.import(&file, format)?;
+                } else {
+                    app.file_name = file;
+                    app.read_file()?;
+                }
+                app.play();
+            }
+        } else if daily {
+            app.daily_game();
+        } else if let Some(seed) = seed {
+            app.new_game(app.difficulty.clone(), app.size, app.variant, seed);
+        }
+        Ok(app)
+    }
+
+    /// Starts a new game, and counts it in the statistics
+    ///
+    /// The larger boards can not always be made as hard as picked, so the game is kept and
+    /// counted at the difficulty the board is rated. It is saved where the picked one is
+    ///
+    /// ## Arguments
+    ///
+    /// * difficulty - The difficulty of the new game
+    /// * size - The size of the new game
+    /// * variant - The variant of the new game
+    /// * seed - The seed to create the board from
+    fn new_game(&mut self, difficulty: Difficulties, size: Size, variant: Variant, seed: u64) {
+        let board = Board::new_seeded(&difficulty, size, variant, seed);
+        let rated = Rating::new(&board)
+            .difficulty()
+            .unwrap_or(difficulty.clone());
+        self.start(board);
+        self.seed = Some(seed);
+        self.file_name = save_name(&difficulty, size, variant);
+        self.puzzle = None;
+        self.stats.start(&rated, size, variant);
+        let saved = self.stats.save(STATS_FILE);
+        self.report(saved, "Could not save the statistics");
+        self.difficulty = rated;
+        self.play();
+    }
+
+    /// Starts a game on a board, forgetting everything about the last game
+    ///
+    /// The cursor goes to the middle, as the last board may have been larger
+    ///
+    /// ## Arguments
+    ///
+    /// * board - The board to play
+    fn start(&mut self, board: Board) {
+        let side = board.size.side();
+        self.won = board.test_filled() && board.test_board();
+        self.board = board;
+        self.history = History::default();
+        self.move_to(side / 2, side / 2);
+        self.timer = Timer::new(Duration::ZERO);
+        self.seed = None;
+        self.day = None;
+        self.format = None;
+        self.comments.clear();
+    }
+
+    /// Imports a puzzle from a file in one of the shared formats, the game is then saved to a
+    /// file of its own so the puzzle is not written over
+    ///
+    /// ## Arguments
+    ///
+    /// * file - The file to import from
+    /// * format - The format of the file
+    ///
+    /// ## Returns
+    ///
+    /// the error if the file can not be read or is not a puzzle, which leaves the game unchanged
+    fn import(&mut self, file: &str, format: Format) -> io::Result<()> {
+        let puzzle = format.read(&std::fs::read_to_string(file)?)?;
+        self.start(puzzle.board);
+        self.format = Some(format);
+        self.comments = puzzle.comments;
+        self.file_name = format!("save-{}", file_stem(file));
+        Ok(())
+    }
+
+    /// Starts the daily puzzle of today, which is saved apart from the other games. If it is
+    /// already saved, it is continued instead
+    ///
+    /// It is always the classic board, so it is the same for everyone
+    fn daily_game(&mut self) {
+        let day = daily::today();
+        let save = std::fs::read_to_string(DAILY_SAVE).unwrap_or_default();
+        let saved = Save::parse(&save).is_ok_and(|save| save.day == Some(day));
+        if saved && self.load(&save).is_ok() {
+            self.file_name = String::from(DAILY_SAVE);
+            self.puzzle = None;
+            self.play();
+            return;
+        }
+
+        let seed = daily::seed(day);
+        self.new_game(DAILY_DIFFICULTY, Size::default(), Variant::default(), seed);
+        self.file_name = String::from(DAILY_SAVE);
+        self.day = Some(day);
+    }
+
+    /// Loads the saved game of a difficulty, of the size and variant of the new games
+    ///
+    /// ## Arguments
+    ///
+    /// * difficulty - The difficulty of the game to load
+    ///
+    /// ## Returns
+    ///
+    /// the error if the save can not be read or is not a game, which leaves the app unchanged
+    fn load_game(&mut self, difficulty: Difficulties) -> io::Result<()> {
+        let file_name = save_name(&difficulty, self.size, self.variant);
+        let save = std::fs::read_to_string(&file_name)?;
+        self.load(&save)?;
+        self.file_name = file_name;
+        // Saves of older versions do not keep the difficulty, the one saved as is used
+        if !Save::is_save(&save) {
+            self.difficulty = difficulty;
+        }
+        self.puzzle = None;
+        self.play();
+        Ok(())
+    }
+
+    /// Plays a puzzle of the collection, from its saved game if it is in progress
+    ///
+    /// ## Arguments
+    ///
+    /// * index - The index of the puzzle
+    ///
+    /// ## Returns
+    ///
+    /// the error if the progress of the collection can not be saved
+    fn play_puzzle(&mut self, index: usize) -> io::Result<()> {
+        let Some(collection) = &self.collection else {
+            return Ok(());
+        };
+        let Some(board) = collection.puzzle(index).cloned() else {
+            return Ok(());
+        };
+        let progress = collection.progress(index);
+        self.file_name = collection.save_file(index);
+        if progress != Progress::Started || self.read_file().is_err() {
+            self.start(board);
+        }
+        self.puzzle = Some(index);
+        let side = self.board.size.side();
+        self.move_to(side / 2, side / 2);
+        self.play();
+        match &mut self.collection {
+            Some(collection) if progress == Progress::Unsolved => {
+                collection.set_progress(index, Progress::Started)
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Used to get the number of choices on the screen picking a difficulty or a puzzle
+    fn choices(&self) -> usize {
+        match (self.state, &self.collection) {
+            (States::Puzzles, Some(collection)) => collection.len(),
+            (States::Puzzles, None) => 0,
+            _ => DIFFICULTIES,
+        }
+    }
+
+    /// Loads the game from the file of the app
+    ///
+    /// ## Returns
+    ///
+    /// the error if the file can not be read or is not a game, which leaves the game unchanged
+    fn read_file(&mut self) -> io::Result<()> {
+        let save = std::fs::read_to_string(&self.file_name)?;
+        self.load(&save)?;
+        Ok(())
+    }
+
+    /// Saves the game to the file of the app
+    fn write_file(&self) -> io::Result<()> {
+        std::fs::write(&self.file_name, self.save())
+    }
+
+    /// Used to get the file the puzzle is exported to, named after the save file
+    fn export_name(&self) -> String {
+        let extension = self.format.unwrap_or(Format::Standard).extension();
+        format!("{}-puzzle.{}", file_stem(&self.file_name), extension)
+    }
+
+    /// Exports the puzzle to its own file, in the format it was imported from or else in the
+    /// common one-line format
+    fn export(&self) -> io::Result<()> {
+        let format = self.format.unwrap_or(Format::Standard);
+        std::fs::write(
+            self.export_name(),
+            format.write(&self.board, &self.comments),
+        )
+    }
+
+    /// Keeps the error of a failed action, to show it instead of stopping the game
+    ///
+    /// ## Arguments
+    ///
+    /// * result - The result of the action
+    /// * action - What failed, to start the message with
+    ///
+    /// ## Returns
+    ///
+    /// a boolean if the action succeeded
+    fn report(&mut self, result: io::Result<()>, action: &str) -> bool {
+        match result {
+            Ok(()) => true,
+            Err(err) => {
+                self.message = Some(format!("{}: {}", action, err));
+                false
             }
+        }
+    }
+
+    /// Used to convert the game to a string to save
+    ///
+    /// ## Returns
+    ///
+    /// the whole game as versioned JSON
+    fn save(&self) -> String {
+        Save {
+            version: SAVE_VERSION,
+            board: self.board.to_string(),
+            variant: self.board.variant,
+            notes: self.board.notes.clone(),
+            row: self.active_row as usize,
+            column: self.active_column as usize,
+            note_mode: self.note_mode,
+            time: self.timer.elapsed().as_secs(),
+            difficulty: self.difficulty.to_num(),
+            seed: self.seed,
+            day: self.day,
+            history: self.history.clone(),
+        }
+        .to_string()
+    }
+
+    /// Used to load a game from a string written by save, or by older versions of the game
+    ///
+    /// ## Arguments
+    ///
+    /// * save - The string to load from
+    ///
+    /// ## Returns
+    ///
+    /// the error if the game can not be read, which leaves the game unchanged
+    fn load(&mut self, save: &str) -> io::Result<()> {
+        if Save::is_save(save) {
+            self.load_save(Save::parse(save)?)
         } else {
-            Self {
-                board: Board::new(&difficulty),
-                active_column: (board::SIDE / 2) as isize,
-                active_row: (board::SIDE / 2) as isize,
-                file_name: format!("save-{}", difficulty),
+            Ok(self.load_lines(save)?)
+        }
+    }
+
+    /// Used to bring back a game exactly as it was saved
+    ///
+    /// ## Arguments
+    ///
+    /// * save - The save to load from
+    ///
+    /// ## Returns
+    ///
+    /// the error if the save does not fit the board, which leaves the game unchanged
+    fn load_save(&mut self, save: Save) -> io::Result<()> {
+        let mut board = Board::parse(&save.board)?;
+        board.set_variant(save.variant);
+        let side = board.size.side();
+        if save.notes.len() != side
+            || save.notes.iter().any(|row| row.len() != side)
+            || save
+                .notes
+                .iter()
+                .flatten()
+                .any(|notes| notes & !board.size.all() != 0)
+            || save.row >= side
+            || save.column >= side
+        {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "the notes or the position do not fit the board",
+            ));
+        }
+        if !save.history.fits(board.size) {
+            return Err(ParseError::InvalidMove.into());
+        }
+        board.notes = save.notes;
+        self.start(board);
+        self.active_row = save.row as isize;
+        self.active_column = save.column as isize;
+        self.note_mode = save.note_mode;
+        self.timer = Timer::new(Duration::from_secs(save.time));
+        self.difficulty = Difficulties::from_num(save.difficulty);
+        self.seed = save.seed;
+        self.day = save.day;
+        self.history = save.history;
+        Ok(())
+    }
+
+    /// Used to load a game from the line format of older versions of the game
+    ///
+    /// Only the first line is needed, so the board alone can be loaded
+    ///
+    /// ## Arguments
+    ///
+    /// * save - The string to load from
+    ///
+    /// ## Returns
+    ///
+    /// the error if the board or a move of the history can not be read, which leaves the game
+    /// unchanged
+    fn load_lines(&mut self, save: &str) -> Result<(), ParseError> {
+        let mut lines = save.lines();
+        let line = lines.next().unwrap_or_default().trim();
+        let mut board = Board::parse(line)?;
+        let mut history = History::default();
+        let (mut seconds, mut seed, mut day) = (0, None, None);
+        for line in lines {
+            if let Some(notes) = line.strip_prefix("notes ") {
+                board.read_notes(notes);
+            } else if let Some(Ok(read)) = line.strip_prefix("time ").map(str::parse) {
+                seconds = read;
+            } else if let Some(Ok(read)) = line.strip_prefix("seed ").map(str::parse) {
+                seed = Some(read);
+            } else if let Some(Ok(read)) = line.strip_prefix("daily ").map(str::parse) {
+                day = Some(read);
+            } else if history.read_line(line) == Some(false) {
+                return Err(ParseError::InvalidMove);
             }
         }
+        if !history.fits(board.size) {
+            return Err(ParseError::InvalidMove);
+        }
+        self.start(board);
+        self.timer = Timer::new(Duration::from_secs(seconds));
+        self.seed = seed;
+        self.day = day;
+        self.history = history;
+        Ok(())
     }
 
     /// Moves the active position up
@@ -88,13 +622,13 @@ impl App {
         self.active_row = if (self.active_row - 1) > -1 {
             self.active_row - 1
         } else {
-            (crate::board::SIDE - 1) as isize
+            (self.board.size.side() - 1) as isize
         }
     }
 
     /// Moves the active position down
     fn down(&mut self) {
-        self.active_row = if (self.active_row + 1) < (crate::board::SIDE as isize) {
+        self.active_row = if (self.active_row + 1) < (self.board.size.side() as isize) {
             self.active_row + 1
         } else {
             0
@@ -106,13 +640,13 @@ impl App {
         self.active_column = if (self.active_column - 1) > -1 {
             self.active_column - 1
         } else {
-            (crate::board::SIDE - 1) as isize
+            (self.board.size.side() - 1) as isize
         }
     }
 
     /// Moves the active position right
     fn right(&mut self) {
-        self.active_column = if (self.active_column + 1) < (crate::board::SIDE as isize) {
+        self.active_column = if (self.active_column + 1) < (self.board.size.side() as isize) {
             self.active_column + 1
         } else {
             0
@@ -135,10 +669,280 @@ impl App {
     /// a boolean if it succeeded
     fn enter(&mut self, digit: usize) -> bool {
         let (row, col) = self.active();
-        self.board.add_number(col, row, digit)
+        if self.note_mode {
+            return self.note(digit);
+        }
+        self.hint.clear();
+        self.mistake = None;
+        self.change(row, col, |board| board.add_number(col, row, digit))
+    }
+
+    /// Toggles a note at the active position, 0 removes all notes
+    ///
+    /// ## Arguments
+    ///
+    /// * digit - the number to toggle
+    ///
+    /// ## Returns
+    ///
+    /// a boolean if it succeeded
+    fn note(&mut self, digit: usize) -> bool {
+        let (row, col) = self.active();
+        self.change(row, col, |board| {
+            if digit == 0 {
+                board.clear_notes(col, row);
+                true
+            } else {
+                board.toggle_note(col, row, digit)
+            }
+        })
+    }
+
+    /// Fills in the notes of every empty position
+    fn fill_notes(&mut self) {
+        let (row, col) = self.active();
+        self.change(row, col, |board| {
+            board.fill_notes();
+            true
+        });
+    }
+
+    /// Makes a change to the board, and adds it to the history
+    ///
+    /// ## Arguments
+    ///
+    /// * row - The row of the position changed
+    /// * col - The column of the position changed
+    /// * change - The change to make, returning if it succeeded
+    ///
+    /// ## Returns
+    ///
+    /// a boolean if it succeeded
+    fn change(&mut self, row: usize, col: usize, change: impl FnOnce(&mut Board) -> bool) -> bool {
+        let old = self.board[(row, col)];
+        let old_notes = self.board.notes.clone();
+        if !change(&mut self.board) {
+            return false;
+        }
+
+        let mut notes = vec![];
+        for (r, (old_each, new_each)) in old_notes.iter().zip(self.board.notes.iter()).enumerate() {
+            for (c, (old_notes, new_notes)) in old_each.iter().zip(new_each.iter()).enumerate() {
+                if old_notes != new_notes {
+                    notes.push((r, c, *old_notes, *new_notes));
+                }
+            }
+        }
+        let new = self.board[(row, col)];
+        if old != new || !notes.is_empty() {
+            self.history.push(Move {
+                row,
+                column: col,
+                old,
+                new,
+                notes,
+            });
+        }
+        true
+    }
+
+    /// Undoes the last move, and moves to its position
+    ///
+    /// ## Returns
+    ///
+    /// a boolean if there was a move to undo
+    fn undo(&mut self) -> bool {
+        match self.history.undo() {
+            Some(change) => {
+                self.board.set_number(change.column, change.row, change.old);
+                for (row, col, old, _) in change.notes.iter() {
+                    self.board.notes[*row][*col] = *old;
+                }
+                self.move_to(change.row, change.column);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Makes the last undone move again, and moves to its position
+    ///
+    /// ## Returns
+    ///
+    /// a boolean if there was a move to redo
+    fn redo(&mut self) -> bool {
+        match self.history.redo() {
+            Some(change) => {
+                self.board.set_number(change.column, change.row, change.new);
+                for (row, col, _, new) in change.notes.iter() {
+                    self.board.notes[*row][*col] = *new;
+                }
+                self.move_to(change.row, change.column);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Shows the board, and starts the clock
+    fn play(&mut self) {
+        self.state = States::Playing;
+        self.in_game = true;
+        self.timer.resume();
+    }
+
+    /// Goes to a screen which hides the board, so the clock is stopped
+    ///
+    /// ## Arguments
+    ///
+    /// * state - The screen to go to
+    fn leave(&mut self, state: States) {
+        self.timer.pause();
+        self.state = state;
+    }
+
+    /// Pauses the game, or continues it if it is paused
+    fn pause(&mut self) {
+        if self.state == States::Paused {
+            self.play();
+        } else {
+            self.leave(States::Paused);
+        }
+    }
+
+    /// Shows the statistics, which go back to the current screen
+    fn show_stats(&mut self) {
+        self.back = self.state;
+        self.leave(States::Stats);
+    }
+
+    /// Goes back from the statistics to the screen they were shown from
+    fn close_stats(&mut self) {
+        if self.back == States::Playing {
+            self.play();
+        } else {
+            self.state = self.back;
+        }
+    }
+
+    /// Tests if the game has just been won, the board being solved for the first time
+    fn just_won(&self) -> bool {
+        !self.won && self.board.test_filled() && self.board.test_board()
+    }
+
+    /// Ends the game as won, and counts it in the statistics
+    ///
+    /// Only generated games are counted, as they are the only ones started with a known
+    /// difficulty. Imported and collection puzzles have no seed
+    fn win(&mut self) {
+        self.timer.pause();
+        self.won = true;
+        self.new_best = false;
+        if self.seed.is_some() {
+            let (size, variant) = (self.board.size, self.board.variant);
+            let time = self.timer.elapsed();
+            self.new_best = self.stats.win(&self.difficulty, size, variant, time);
+            let saved = self.stats.save(STATS_FILE);
+            self.report(saved, "Could not save the statistics");
+        }
+        if let Some(day) = self.day {
+            self.daily.complete(day, self.timer.elapsed());
+            let saved = self.daily.save(DAILY_FILE);
+            self.report(saved, "Could not save the daily puzzle");
+        }
+        if let (Some(collection), Some(puzzle)) = (&mut self.collection, self.puzzle) {
+            let saved = collection.set_progress(puzzle, Progress::Solved);
+            self.report(saved, "Could not save the progress of the collection");
+        }
+        self.in_game = false;
+        self.state = States::Won;
+    }
+
+    /// Sets the active position, and removes the hint as it may no longer be right
+    fn move_to(&mut self, row: usize, col: usize) {
+        self.active_row = row as isize;
+        self.active_column = col as isize;
+        self.hint.clear();
+        self.mistake = None;
+    }
+
+    /// Gets the position the hint places a value at, or the wrong entry it found instead
+    fn hint_position(&self) -> Option<(usize, usize)> {
+        self.hint
+            .last()
+            .and_then(|step| step.placement)
+            .map(|(row, col, _)| (row, col))
+            .or(self.mistake)
+    }
+
+    /// Finds the next value to place, or places it if it is already found
+    ///
+    /// An entry not matching the solution is shown instead, as no step can be explained while
+    /// it is on the board
+    ///
+    /// ## Returns
+    ///
+    /// a boolean if a value was found or placed
+    fn hint(&mut self) -> bool {
+        if let Some((row, col, value)) = self.hint.last().and_then(|step| step.placement) {
+            self.hint.clear();
+            return self.change(row, col, |board| board.add_number(col, row, value + 1));
+        }
+
+        let solution = match self.board.solve() {
+            Some(solution) => solution,
+            None => return false,
+        };
+        let side = self.board.size.side();
+        self.hint.clear();
+        self.mistake = (0..side)
+            .flat_map(|row| (0..side).map(move |col| (row, col)))
+            .find(|position| {
+                self.board[*position].is_some() && self.board[*position] != solution[*position]
+            });
+        if self.mistake.is_some() {
+            return true;
+        }
+
+        let mut solver = Solver::new(&self.board.tries, self.board.size, self.board.units());
+        while let Some(step) = solver.next_step() {
+            solver.apply(&step);
+            let placed = step.placement.is_some();
+            self.hint.push(step);
+            if placed {
+                return true;
+            }
+        }
+        // No value could be placed by the known techniques
+        self.hint.clear();
+        false
     }
 }
 
+/// Function to get the notes of a position laid out like the positions of a square
+///
+/// ## Arguments
+///
+/// * notes - The notes as bitflags
+/// * size - The size of the board
+fn notes_text<'a>(notes: u16, size: Size) -> Vec<Spans<'a>> {
+    (0..size.square_rows())
+        .map(|line| {
+            let digits = (0..size.square_columns())
+                .map(|each| {
+                    let value = line * size.square_columns() + each;
+                    if notes & (1 << value) != 0 {
+                        board::symbol(value).to_string()
+                    } else {
+                        String::from(" ")
+                    }
+                })
+                .collect::<Vec<String>>();
+            Spans::from(digits.join(" "))
+        })
+        .collect()
+}
+
 /// Used to get the block of the current cell
 ///
 /// Changes based on if the cell is active or not
@@ -146,27 +950,36 @@ impl App {
 /// ## Arguments
 ///
 /// * bg_color - The background-color to use
-fn block<'a>(bg_color: Color) -> Block<'a> {
+/// * borders - If the cell has borders, which only fit on the larger tiles
+fn block<'a>(bg_color: Color, borders: bool) -> Block<'a> {
     let color = bg_color;
     Block::default()
         .style(Style::default().bg(color).fg(color))
-        .borders(Borders::ALL)
+        .borders(if borders { Borders::ALL } else { Borders::NONE })
         .border_style(Style::default())
         .border_type(BorderType::Plain)
 }
 
 /// Used to get the text-style of the current cell
 ///
-/// The foreground color is based on if the number is in the "emtpy"-set or the "tries"-set
+/// The foreground color is based on if the number is in the "emtpy"-set or the "tries"-set,
+/// or if it conflicts with another number
 /// The background color is based on if the current cell is active or not, together with also
 /// changing if the number should be bold or not
 ///
 /// ## Arguments
 ///
 /// * bg_color - The back-ground color to use
-fn text_style(old: bool, is_active: bool, bg_color: Color) -> Style {
+fn text_style(old: bool, is_active: bool, conflict: bool, bg_color: Color) -> Style {
+    let fg_color = if conflict {
+        Color::Red
+    } else if old {
+        Color::Blue
+    } else {
+        Color::Black
+    };
     Style::default()
-        .fg(if old { Color::Blue } else { Color::Black })
+        .fg(fg_color)
         .bg(if is_active { Color::Cyan } else { bg_color })
         .add_modifier(if is_active {
             Modifier::BOLD
@@ -182,46 +995,92 @@ fn text_style(old: bool, is_active: bool, bg_color: Color) -> Style {
 /// * f - is the frame to be written to
 /// * app - is the app to be run from
 fn board<B: Backend>(f: &mut Frame<B>, window: Rect, app: &mut App) {
+    let size = app.board.size;
+    let sudoku_size = sudoku_size(size);
     let rects = Rect {
-        x: window.x + (SUDOKU_SIZE / 4),
-        y: window.y + (SUDOKU_SIZE / 8),
-        width: SUDOKU_SIZE * 2,
-        height: SUDOKU_SIZE,
+        x: window.x + (sudoku_size / 4),
+        y: window.y + (sudoku_size / 8),
+        width: sudoku_size * 2,
+        height: sudoku_size,
     };
 
-    // Splits the alloted space into a 3x3
-    let large_cells = split_in_3x3(rects);
+    // Hides the board, so it can not be looked at while the clock is stopped
+    if app.state == States::Paused {
+        let paragraph = Paragraph::new(vec![
+            Spans::from(String::from("Paused")),
+            Spans::from(String::from("P to continue")),
+        ])
+        .alignment(Alignment::Center)
+        .block(Block::default().borders(Borders::ALL));
+        f.render_widget(paragraph, rects.intersection(window));
+        return;
+    }
+
+    // Splits the alloted space into the squares
+    let across = size.side() / size.square_columns();
+    let large_cells = split_in_grid(rects, size.side() / size.square_rows(), across);
+    let conflicts = app.board.conflicts();
 
     for (r, row_rect) in large_cells.into_iter().enumerate() {
-        // Splits each rectangle into a 3x3
-        let col_rects = split_in_3x3(row_rect);
+        // Splits each square into its positions
+        let col_rects = split_in_grid(row_rect, size.square_rows(), size.square_columns());
 
         for (c, col_rect) in col_rects.into_iter().enumerate() {
-            let bg_color = match r % 2 {
+            // Next to each other the squares take turns
+            let bg_color = match (r / across + r % across) % 2 {
                 0 => Color::Gray,
                 _ => Color::White,
             };
 
             // Convert to "proper" axis
-            let (c, r) = square_to_point(r, c);
+            let (r, c) = size.square_position(r, c);
 
             let (value, old) = get_string_value(r, c, &app.board);
             let text = format!(" {} ", value);
 
             let is_active = app.active() == (r, c);
+            let on_diagonal = r == c || r + c == size.side() - 1;
+            let bg_color = if app.hint_position() == Some((r, c)) {
+                Color::Yellow
+            } else if on_diagonal && app.board.variant.has_diagonals() {
+                Color::LightGreen
+            } else {
+                bg_color
+            };
+
+            let small = tile_size(size) < TILE_SIZE;
+            f.render_widget(block(bg_color, !small), col_rect);
 
+            let notes = app.board.notes[r][c];
+            if app.board[(r, c)].is_none() && notes != 0 && small {
+                // The notes do not fit, so they are listed for the active position instead
+                let paragraph = Paragraph::new("…")
+                    .alignment(Alignment::Center)
+                    .style(text_style(old, is_active, false, bg_color).fg(Color::DarkGray));
+                f.render_widget(paragraph, col_rect);
+                continue;
+            }
+            if app.board[(r, c)].is_none() && notes != 0 {
+                // The notes fill the whole cell, border included
+                let paragraph = Paragraph::new(notes_text(notes, size))
+                    .alignment(Alignment::Center)
+                    .style(text_style(old, is_active, false, bg_color).fg(Color::DarkGray));
+                f.render_widget(paragraph, col_rect);
+                continue;
+            }
+
+            let conflict = conflicts.contains(&(r, c));
             let paragraph = Paragraph::new(text)
                 .alignment(Alignment::Center)
-                .style(text_style(old, is_active, bg_color));
+                .style(text_style(old, is_active, conflict, bg_color));
 
             let text_rect = Rect {
                 x: col_rect.x + 1,
-                y: col_rect.y + 1,
+                y: col_rect.y + col_rect.height.saturating_sub(1) / 2,
                 width: 3,
                 height: 1,
             };
 
-            f.render_widget(block(bg_color), col_rect);
             f.render_widget(paragraph, text_rect);
         }
 
@@ -234,18 +1093,7 @@ fn board<B: Backend>(f: &mut Frame<B>, window: Rect, app: &mut App) {
     }
 }
 
-/// Function to split a field into 3x3
-///
-/// see [MitchelPaulin](https://github.com/MitchelPaulin/sudoku-rs/blob/main/src/ui.rs) for
-/// implementaiton
-fn square_to_point(square_number: usize, cell_numbe: usize) -> (usize, usize) {
-    let col = (square_number % 3) * 3 + cell_numbe % 3;
-    let row = (square_number / 3) * 3 + cell_numbe / 3;
-
-    (col, row)
-}
-
-/// Function to split a field into 3x3
+/// Function to split a field into a grid, row by row
 ///
 /// see [MitchelPaulin](https://github.com/MitchelPaulin/sudoku-rs/blob/main/src/ui.rs) for
 /// implementaiton
@@ -253,21 +1101,23 @@ fn square_to_point(square_number: usize, cell_numbe: usize) -> (usize, usize) {
 /// ## Arguments
 ///
 /// * area - The area to split
+/// * rows - The number of rows to split into
+/// * columns - The number of columns to split into
 ///
 /// ## Returns
 ///
 /// a Vec of rects defining the split area
-fn split_in_3x3(area: Rect) -> Vec<Rect> {
+fn split_in_grid(area: Rect, rows: usize, columns: usize) -> Vec<Rect> {
     let mut ret_rects = vec![];
 
-    let rows = split_rect_in_3(area, Direction::Vertical);
+    let rows = split_rect(area, Direction::Vertical, rows);
     for row in rows {
-        ret_rects.extend(split_rect_in_3(row, Direction::Horizontal));
+        ret_rects.extend(split_rect(row, Direction::Horizontal, columns));
     }
     ret_rects
 }
 
-/// Function to split a rectangle in 3
+/// Function to split a rectangle in equal parts
 ///
 /// see [MitchelPaulin](https://github.com/MitchelPaulin/sudoku-rs/blob/main/src/ui.rs) for
 /// implementaiton
@@ -275,20 +1125,14 @@ fn split_in_3x3(area: Rect) -> Vec<Rect> {
 /// ## Arguments
 /// * area - is the Rectangle to split
 /// * dir - is the direction to split in
+/// * parts - is the number of parts to split into
 ///
 /// ## Returns
 /// a Vec of Rect
-fn split_rect_in_3(area: Rect, dir: Direction) -> Vec<Rect> {
+fn split_rect(area: Rect, dir: Direction, parts: usize) -> Vec<Rect> {
     Layout::default()
         .direction(dir)
-        .constraints(
-            [
-                Constraint::Ratio(1, 3),
-                Constraint::Ratio(1, 3),
-                Constraint::Ratio(1, 3),
-            ]
-            .as_ref(),
-        )
+        .constraints(vec![Constraint::Ratio(1, parts as u32); parts])
         .split(area)
 }
 
@@ -299,12 +1143,28 @@ fn split_rect_in_3(area: Rect, dir: Direction) -> Vec<Rect> {
 /// * f - The frame used to write into
 /// * window - is the alloted window to be contained in
 /// * status - is a bitflag of different statuses
-fn info_window<B: Backend>(f: &mut Frame<B>, window: Rect, status: u8) {
+/// * app - is the app to show the hint and mode of
+fn info_window<B: Backend>(f: &mut Frame<B>, window: Rect, status: u8, app: &App) {
     let rect = Layout::default()
         .direction(Direction::Horizontal)
         .constraints([Constraint::Percentage(50), Constraint::Percentage(50)].as_ref())
         .split(window);
-    let info_par = Paragraph::new(vec![
+    let mut info = vec![
+        Spans::from(format!("Time: {} \n", app.timer)),
+        Spans::from(format!(
+            "Seed: {} \n",
+            app.seed.map_or(String::from("-"), |seed| seed.to_string())
+        )),
+        Spans::from(format!(
+            "Difficulty: {} \n",
+            app.seed
+                .map_or(String::from("-"), |_| app.difficulty.to_string())
+        )),
+        Spans::from(format!(
+            "Daily puzzle: {} \n",
+            app.day.map_or(String::from("no"), |day| day.to_string())
+        )),
+        Spans::from(format!("Rules: {} \n", app.board.variant.name())),
         Spans::from(format!(
             "Is board correct?: {}\n",
             if status & 0x1 == 0x1 { "true" } else { "false" },
@@ -333,15 +1193,70 @@ fn info_window<B: Backend>(f: &mut Frame<B>, window: Rect, status: u8) {
                 "false"
             }
         )),
-        Spans::from(String::from("")),
-    ])
-    .block(Block::default().borders(Borders::ALL))
-    .alignment(Alignment::Left);
+        Spans::from(format!(
+            "Notes mode: {} \n",
+            if app.note_mode { "on" } else { "off" }
+        )),
+    ];
+    // The notes do not fit on the small tiles, so the ones of the active position are listed
+    if tile_size(app.board.size) < TILE_SIZE {
+        let (row, col) = app.active();
+        let notes = app.board.notes[row][col];
+        let symbols = (0..app.board.size.side())
+            .filter(|value| notes & (1 << value) != 0)
+            .map(|value| board::symbol(value).to_string())
+            .collect::<Vec<String>>();
+        info.push(Spans::from(format!("Notes: {}", symbols.join(" "))));
+    }
+    if status & 0x10 == 0x10 {
+        info.push(Spans::from(format!(
+            "Puzzle exported to {}",
+            app.export_name()
+        )));
+    }
+    if let Some(message) = &app.message {
+        info.push(Spans::from(format!("Error: {}", message)));
+    }
+    if status & 0x8 == 0x8 {
+        info.push(Spans::from(String::from("Hint: No logical step found")));
+    }
+    if let Some((row, col)) = app.mistake {
+        info.push(Spans::from(format!(
+            "Hint: the {} in r{}c{} is wrong, remove it first",
+            app.board[(row, col)].map_or('?', board::symbol),
+            row + 1,
+            col + 1
+        )));
+    }
+    // Only the last steps fit, and the last step is the one placing the value
+    let skipped = app.hint.len().saturating_sub(HINT_STEPS);
+    for step in app.hint[skipped..].iter() {
+        info.push(Spans::from(format!("Hint: {}", step)));
+    }
+    let info_par = Paragraph::new(info)
+        .block(Block::default().borders(Borders::ALL))
+        .alignment(Alignment::Left)
+        .wrap(Wrap { trim: true });
+    let side = app.board.size.side();
     let paragraph = Paragraph::new(vec![
         Spans::from(String::from("↑↓←→ for up/down/left/right")),
-        Spans::from(String::from("1-9 for adding a number")),
+        Spans::from(if side > 9 {
+            format!(
+                "1-9 and Shift+A-{} for adding a number",
+                board::symbol(side - 1)
+            )
+        } else {
+            format!("1-{} for adding a number", side)
+        }),
         Spans::from(String::from("Space or 0 for removing a number")),
         Spans::from(String::from("S to save L to load")),
+        Spans::from(String::from("E to export the puzzle")),
+        Spans::from(String::from("U to undo R to redo")),
+        Spans::from(String::from("P to pause T for statistics")),
+        Spans::from(String::from("Esc for the menu")),
+        Spans::from(String::from("N to switch between numbers and notes")),
+        Spans::from(String::from("C to fill in all notes")),
+        Spans::from(String::from("H for a hint, again to place it")),
         Spans::from(String::from("Q to Close")),
     ])
     .block(Block::default().borders(Borders::ALL))
@@ -352,6 +1267,9 @@ fn info_window<B: Backend>(f: &mut Frame<B>, window: Rect, status: u8) {
 
 const CLEAR_FLAG: u8 = 0x1;
 
+/// The number of steps of a hint to show
+const HINT_STEPS: usize = 3;
+
 /// Handles the input of the keys
 /// Placed here due to being quite the few
 ///
@@ -366,6 +1284,15 @@ const CLEAR_FLAG: u8 = 0x1;
 fn read_key(key: KeyCode, app: &mut App, status: u8) -> u8 {
     let mut status = status;
     match key {
+        KeyCode::Char('p') => {
+            app.pause();
+        }
+        KeyCode::Char('t') => {
+            app.show_stats();
+        }
+        KeyCode::Esc => {
+            app.leave(States::Menu);
+        }
         KeyCode::Left => {
             app.left();
         }
@@ -379,15 +1306,46 @@ fn read_key(key: KeyCode, app: &mut App, status: u8) -> u8 {
             app.down();
         }
         KeyCode::Char('s') => {
-            std::fs::write(&app.file_name, app.board.to_string()).expect("Write failed");
             status &= CLEAR_FLAG;
-            status |= 0x2;
+            let saved = app.write_file();
+            let action = format!("Could not save to {}", app.file_name);
+            if app.report(saved, &action) {
+                status |= 0x2;
+            }
+        }
+        KeyCode::Char('e') => {
+            status &= CLEAR_FLAG;
+            let exported = app.export();
+            let action = format!("Could not export to {}", app.export_name());
+            if app.report(exported, &action) {
+                status |= 0x10;
+            }
         }
         KeyCode::Char('l') => {
-            app.board =
-                Board::from_string(std::fs::read_to_string(&app.file_name).expect("No such file"));
             status &= CLEAR_FLAG;
-            status |= 0x4;
+            let loaded = app.read_file();
+            let action = format!("Could not load {}", app.file_name);
+            if app.report(loaded, &action) {
+                status |= 0x4;
+            }
+        }
+        KeyCode::Char('n') => {
+            app.note_mode = !app.note_mode;
+        }
+        KeyCode::Char('c') => {
+            app.fill_notes();
+        }
+        KeyCode::Char('u') => {
+            app.undo();
+        }
+        KeyCode::Char('r') => {
+            app.redo();
+        }
+        KeyCode::Char('h') => {
+            status &= CLEAR_FLAG;
+            if !app.hint() {
+                status |= 0x8;
+            }
         }
         KeyCode::Char('1') => {
             app.enter(1);
@@ -425,6 +1383,13 @@ fn read_key(key: KeyCode, app: &mut App, status: u8) -> u8 {
             app.enter(9);
             status &= CLEAR_FLAG;
         }
+        // The numbers above nine, as letters so they do not clash with the other keys
+        KeyCode::Char(character @ 'A'..='G') => {
+            if let Some(value) = board::from_symbol(character) {
+                app.enter(value + 1);
+            }
+            status &= CLEAR_FLAG;
+        }
         KeyCode::Char('0') | KeyCode::Char(' ') => {
             app.enter(0);
             status &= CLEAR_FLAG;
@@ -434,16 +1399,234 @@ fn read_key(key: KeyCode, app: &mut App, status: u8) -> u8 {
     status
 }
 
+/// Handles the input of the keys on the screens other than the game
+///
+/// ## Arguments
+/// * key - the key to match against
+/// * app - the app the handle onto
+/// * status - the current status of the game
+///
+/// ## Returns
+///
+/// The status again, which is reset when a game is started
+fn read_screen_key(key: KeyCode, app: &mut App, status: u8) -> u8 {
+    match (app.state, key) {
+        (States::Menu | States::Won, KeyCode::Char('n')) => {
+            app.state = States::New;
+        }
+        (States::Menu, KeyCode::Char('d')) => {
+            app.daily_game();
+            return 0x0;
+        }
+        (States::Menu, KeyCode::Char('l')) => {
+            app.state = States::Load;
+        }
+        (States::Menu, KeyCode::Char('c')) if app.in_game => {
+            app.play();
+        }
+        (States::Menu | States::Won, KeyCode::Char('t')) => {
+            app.show_stats();
+        }
+        (States::Menu | States::Won, KeyCode::Char('p')) if app.collection.is_some() => {
+            app.selected = app.puzzle.unwrap_or(0);
+            app.state = States::Puzzles;
+        }
+        (States::New | States::Load | States::Puzzles, KeyCode::Up) => {
+            app.selected = app.selected.saturating_sub(1);
+        }
+        (States::New | States::Load | States::Puzzles, KeyCode::Down) => {
+            app.selected = (app.selected + 1).min(app.choices().saturating_sub(1));
+        }
+        (States::New, KeyCode::Enter) => {
+            let difficulty = Difficulties::from_num(app.selected as u8);
+            app.new_game(difficulty, app.size, app.variant, rand::random());
+            return 0x0;
+        }
+        (States::Puzzles, KeyCode::Enter) => {
+            let played = app.play_puzzle(app.selected);
+            app.report(played, "Could not save the progress of the collection");
+            return 0x0;
+        }
+        (States::Load, KeyCode::Enter) => {
+            let difficulty = Difficulties::from_num(app.selected as u8);
+            let action = format!("Could not load the {} game", difficulty);
+            let loaded = app.load_game(difficulty);
+            return if app.report(loaded, &action) {
+                0x4
+            } else {
+                status
+            };
+        }
+        (States::New | States::Load | States::Puzzles | States::Won, KeyCode::Esc) => {
+            app.state = States::Menu;
+        }
+        (States::Paused, KeyCode::Char('p')) => {
+            app.pause();
+        }
+        (States::Stats, KeyCode::Char('t') | KeyCode::Esc) => {
+            app.close_stats();
+        }
+        _ => {}
+    }
+    status
+}
+
+/// The number of difficulties to pick from
+const DIFFICULTIES: usize = 3;
+
+/// The number of puzzles of a collection shown at once
+const PUZZLE_ROWS: usize = 10;
+
+/// Used to write a screen of centered lines, such as the menus
+///
+/// ## Arguments
+///
+/// * f - The frame used to write into
+/// * lines - The lines to write
+fn screen<B: Backend>(f: &mut Frame<B>, lines: Vec<Spans>) {
+    let paragraph = Paragraph::new(lines)
+        .block(Block::default().borders(Borders::ALL))
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true });
+    f.render_widget(paragraph, f.size());
+}
+
+/// Used to get the lines of the screens other than the game
+///
+/// ## Arguments
+///
+/// * app - The app to show the screen of
+fn screen_lines<'a>(app: &App) -> Vec<Spans<'a>> {
+    let mut lines = vec![Spans::from(String::from("Sudoku")), Spans::from("")];
+    match app.state {
+        States::Menu => {
+            if app.in_game {
+                lines.push(Spans::from(String::from("C to continue the game")));
+            }
+            lines.push(Spans::from(String::from("N for a new game")));
+            lines.push(Spans::from(match app.daily.time(daily::today()) {
+                Some(time) => format!("D for today's puzzle, done in {}", format_time(time)),
+                None => String::from("D for today's puzzle, not done yet"),
+            }));
+            lines.push(Spans::from(String::from("L to load a saved game")));
+            if app.collection.is_some() {
+                lines.push(Spans::from(String::from(
+                    "P to pick a puzzle of the collection",
+                )));
+            }
+            lines.push(Spans::from(String::from("T for statistics")));
+        }
+        States::Puzzles => {
+            let count = app.choices();
+            let solved = (0..count)
+                .filter(|index| {
+                    app.collection
+                        .as_ref()
+                        .map(|collection| collection.progress(*index))
+                        == Some(Progress::Solved)
+                })
+                .count();
+            lines.push(Spans::from(format!(
+                "Pick a puzzle of the collection, {} of {} solved",
+                solved, count
+            )));
+            lines.push(Spans::from(""));
+            // Only the puzzles around the one picked fit
+            let first = app
+                .selected
+                .saturating_sub(PUZZLE_ROWS / 2)
+                .min(count.saturating_sub(PUZZLE_ROWS));
+            for index in first..count.min(first + PUZZLE_ROWS) {
+                let progress = app
+                    .collection
+                    .as_ref()
+                    .map_or(Progress::Unsolved, |collection| collection.progress(index));
+                let style = if index == app.selected {
+                    Style::default().add_modifier(Modifier::REVERSED)
+                } else {
+                    Style::default()
+                };
+                lines.push(Spans::from(Span::styled(
+                    format!(" {:>4}. {:<11} ", index + 1, progress),
+                    style,
+                )));
+            }
+            lines.push(Spans::from(""));
+            lines.push(Spans::from(String::from("↑↓ to pick, Enter to play")));
+            lines.push(Spans::from(String::from("Esc for the menu")));
+        }
+        States::New | States::Load => {
+            lines.push(Spans::from(if app.state == States::New {
+                format!("Pick the difficulty of the new {} game", game_name(app))
+            } else {
+                format!("Pick the difficulty of the saved {} game", game_name(app))
+            }));
+            lines.push(Spans::from(""));
+            for level in 0..DIFFICULTIES {
+                let style = if level == app.selected {
+                    Style::default().add_modifier(Modifier::REVERSED)
+                } else {
+                    Style::default()
+                };
+                lines.push(Spans::from(Span::styled(
+                    format!(" {} ", Difficulties::from_num(level as u8)),
+                    style,
+                )));
+            }
+            lines.push(Spans::from(""));
+            lines.push(Spans::from(String::from("↑↓ to pick, Enter to start")));
+            lines.push(Spans::from(String::from("Esc for the menu")));
+        }
+        States::Won => {
+            lines.push(Spans::from(format!(
+                "Congratulations, you won! Time: {}",
+                app.timer
+            )));
+            if app.new_best {
+                lines.push(Spans::from(String::from("That is a new best time!")));
+            }
+            lines.push(Spans::from(""));
+            lines.extend(app.stats.lines().into_iter().map(Spans::from));
+            lines.push(Spans::from(""));
+            lines.push(Spans::from(String::from("N for a new game")));
+            if app.collection.is_some() {
+                lines.push(Spans::from(String::from("P to pick another puzzle")));
+            }
+            lines.push(Spans::from(String::from("T for statistics")));
+            lines.push(Spans::from(String::from("Esc for the menu")));
+        }
+        _ => {
+            lines.push(Spans::from(String::from("Statistics")));
+            lines.push(Spans::from(""));
+            lines.extend(app.stats.lines().into_iter().map(Spans::from));
+            lines.push(Spans::from(""));
+            lines.push(Spans::from(String::from("T to go back")));
+        }
+    }
+    lines.push(Spans::from(String::from("Q to Close")));
+    if let Some(message) = &app.message {
+        lines.push(Spans::from(""));
+        lines.push(Spans::from(format!("Error: {}", message)));
+    }
+    lines
+}
+
 pub fn run_app(terminal: &mut Term, mut app: App) -> io::Result<()> {
     // Bitflags
     // 0x1 = Is the solution correct?
     // 0x2 = Is it saved?
     // 0x4 = Is it loaded
+    // 0x8 = No hint found
+    // 0x10 = Is the puzzle exported
     // 0x20 = all positions filled
     // 0x40 = all positions correct
     let mut status: u8 = 0x0;
-    loop {
+    while app.state != States::Quit {
         terminal.render(&mut |f: &mut Frame<CrosstermBackend<Stdout>>| {
+            if app.state != States::Playing && app.state != States::Paused {
+                screen(f, screen_lines(&app));
+                return;
+            }
             let outer_block = Block::default().borders(Borders::ALL);
             f.render_widget(outer_block, f.size());
             let layout = Layout::default()
@@ -461,46 +1644,262 @@ pub fn run_app(terminal: &mut Term, mut app: App) -> io::Result<()> {
                 .split(layout[1]);
 
             board(f, center[0], &mut app);
-            info_window(f, center[1], status);
+            info_window(f, center[1], status, &app);
         });
 
+        // Waits for a key at most a tick, so the clock is drawn again
+        if !event::poll(TICK_RATE)? {
+            continue;
+        }
         if let Event::Key(key) = event::read()? {
-            status = match key.code {
-                KeyCode::Char('q') => return Ok(()),
-                rest => read_key(rest, &mut app, status),
+            app.message = None;
+            status = match (app.state, key.code) {
+                (_, KeyCode::Char('q')) => {
+                    app.state = States::Quit;
+                    status
+                }
+                (States::Playing, rest) => read_key(rest, &mut app, status),
+                (_, rest) => read_screen_key(rest, &mut app, status),
             }
         }
 
+        if app.state != States::Playing {
+            continue;
+        }
+
         if app.board.test_board() {
             status |= 0x40;
         }
 
-        if app.board.test_board() {
-            status |= 0x1;
-            break;
-        }
-    }
-    loop {
-        terminal.render(&mut |frame| {
-            let message = format!("Congratulations, you won!");
-            let rect = Rect {
-                x: frame.size().x,
-                y: frame.size().y,
-                width: frame.size().width,
-                height: frame.size().height,
-            };
-            let paragraph = Paragraph::new(vec![
-                Spans::from(String::from("Q to Close")),
-                Spans::from(message),
-            ]);
-            frame.render_widget(paragraph, rect);
+        if app.just_won() {
+            app.win();
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod app_test {
+    use super::*;
+
+    /// A board in the line format, with a 4 entered in the top row
+    const BOARD: &str =
+        "ec3.g....f..aie....ih....f.h...f...cd..h.c..ag...b...f.f....bh....dai..e....h..gi";
+
+    /// Gets an app without a game, as it is when the menu is shown
+    fn get_app() -> App {
+        App::new(
+            Difficulties::Easy,
+            Size::default(),
+            Variant::default(),
+            None,
+            None,
+            false,
+        )
+        .expect("No file is read")
+    }
+
+    /// Gets a save in the line format of older versions, where the 4 was the last move made
+    fn get_lines(change: &str) -> String {
+        format!(
+            "{}\nnotes {}\ntime 125\nseed 42\n{}\n",
+            BOARD,
+            ["0"; 81].join(","),
+            change
+        )
+    }
+
+    #[test]
+    fn test_load_lines() {
+        let mut app = get_app();
+
+        assert!(app.load(&get_lines("done 0 2 . 3 0:3:8:0")).is_ok());
+        assert_eq!(app.board[(0, 2)], Some(3));
+        assert_eq!(app.seed, Some(42));
+        assert!(app.timer.elapsed() >= Duration::from_secs(125));
+
+        assert!(app.undo());
+        assert_eq!(app.board[(0, 2)], None);
+        assert_eq!(app.board.notes[0][3], 8);
+        assert_eq!((app.active_row, app.active_column), (0, 2));
+        assert!(!app.undo());
+
+        assert!(app.redo());
+        assert_eq!(app.board[(0, 2)], Some(3));
+    }
+
+    #[test]
+    fn test_is_puzzle() {
+        let puzzle = BOARD
+            .to_uppercase()
+            .replace(|c: char| c.is_ascii_alphabetic(), "1");
+
+        assert!(is_puzzle(&puzzle));
+        assert!(!is_puzzle(BOARD));
+        assert!(!is_puzzle(&get_lines("")));
+
+        let mut app = get_app();
+        app.load(&get_lines("")).expect("The save is valid");
+        let save = app.save();
+
+        assert!(!is_puzzle(&save));
+    }
+
+    #[test]
+    fn test_load_smaller() {
+        let mut app = get_app();
+        app.move_to(8, 8);
+
+        assert!(app.load("a..d.d...c....a.").is_ok());
+        assert_eq!((app.active_row, app.active_column), (2, 2));
+        assert!(app.enter(4));
+        assert_eq!(app.board[(2, 2)], Some(3));
+    }
+
+    #[test]
+    fn test_load_save() {
+        let mut history = History::default();
+        history.push(Move {
+            row: 0,
+            column: 2,
+            old: None,
+            new: Some(3),
+            notes: vec![],
         });
+        let save = Save {
+            version: SAVE_VERSION,
+            board: String::from(BOARD),
+            variant: Variant::X,
+            notes: vec![vec![0; 9]; 9],
+            row: 4,
+            column: 5,
+            note_mode: true,
+            time: 125,
+            difficulty: 2,
+            seed: Some(42),
+            day: None,
+            history,
+        };
+        let mut app = get_app();
 
-        if let Event::Key(key) = event::read()? {
-            match key.code {
-                KeyCode::Char('q') => return Ok(()),
-                _ => {}
+        assert!(app.load(&save.to_string()).is_ok());
+        assert_eq!(app.board.variant, Variant::X);
+        assert_eq!((app.active_row, app.active_column), (4, 5));
+        assert!(app.note_mode);
+        assert_eq!(app.difficulty, Difficulties::Hard);
+
+        assert!(app.undo());
+        assert_eq!(app.board[(0, 2)], None);
+
+        // Saving and loading again keeps the game as it is
+        let string = app.save();
+        let mut read = get_app();
+
+        assert!(read.load(&string).is_ok());
+        assert_eq!(read.save(), string);
+    }
+
+    #[test]
+    fn test_load_corrupt_history() {
+        let mut app = get_app();
+        app.load(&get_lines("done 0 2 . 3 -"))
+            .expect("The save is read");
+
+        for change in [
+            "done 9 2 . 2 -",
+            "done 0 2 . 16 -",
+            "undone 0 2 . 2 0:3:512:0",
+            "done x",
+        ] {
+            assert!(app.load(&get_lines(change)).is_err());
+        }
+
+        let mut save = Save::parse(&app.save()).expect("The save is JSON");
+        save.history.push(Move {
+            row: 0,
+            column: 3,
+            old: None,
+            new: Some(9),
+            notes: vec![],
+        });
+
+        assert!(app.load(&save.to_string()).is_err());
+        // The game is left as it was
+        assert_eq!(app.board[(0, 2)], Some(3));
+        assert!(app.undo());
+        assert!(!app.undo());
+    }
+
+    #[test]
+    fn test_win() {
+        let mut app = get_app();
+        let board = Board::new_seeded(&Difficulties::Easy, Size::default(), Variant::default(), 42);
+        let solution = board.solve().expect("The board has a solution");
+        app.start(solution.clone());
+
+        // A game loaded already won is not won again
+        assert!(!app.just_won());
+
+        let mut board = board;
+        let mut blanks = vec![];
+        for row in 0..9 {
+            for column in 0..9 {
+                if board[(row, column)].is_none() {
+                    board.set_number(column, row, solution[(row, column)]);
+                    blanks.push((row, column));
+                }
             }
         }
+        let (row, column) = blanks[0];
+        board.set_number(column, row, None);
+        app.start(board);
+        app.move_to(row, column);
+
+        assert!(!app.just_won());
+        assert!(app.enter(solution[(row, column)].expect("The solution is filled") + 1));
+        assert!(app.just_won());
+
+        let stats = app.stats.clone();
+        app.win();
+
+        assert!(!app.just_won());
+        // Without a seed the puzzle is not one of the generated games counted
+        assert_eq!(app.stats, stats);
+        assert!(!app.new_best);
+
+        assert!(app.undo());
+        assert!(app.redo());
+        assert!(!app.just_won());
+    }
+
+    #[test]
+    fn test_hint() {
+        let mut app = get_app();
+        let board = Board::new_seeded(&Difficulties::Easy, Size::default(), Variant::default(), 42);
+        app.start(board);
+
+        assert!(app.hint());
+
+        let (row, column) = app.hint_position().expect("The hint places a number");
+
+        assert_eq!(app.board[(row, column)], None);
+        assert!(app.hint());
+        assert!(app.board[(row, column)].is_some());
+        assert!(app.hint.is_empty());
+
+        assert!(app.undo());
+        assert_eq!(app.board[(row, column)], None);
+
+        // A wrong entry is pointed out instead, and left for the player to remove
+        let solution = app.board.solve().expect("The board has a solution");
+        let wrong = solution[(row, column)].map(|value| (value + 1) % 9 + 1);
+        app.move_to(row, column);
+        app.enter(wrong.expect("The solution is filled"));
+
+        assert!(app.hint());
+        assert_eq!(app.hint_position(), Some((row, column)));
+        assert!(app.hint.is_empty());
+        assert!(app.hint());
+        assert_eq!(app.board[(row, column)], wrong.map(|digit| digit - 1));
     }
 }
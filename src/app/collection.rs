@@ -0,0 +1,249 @@
+use std::{
+    io,
+    path::{Path, PathBuf},
+};
+
+use crate::board::Board;
+
+/// How far a puzzle of a collection is played
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Progress {
+    Unsolved,
+    Started,
+    Solved,
+}
+
+impl std::fmt::Display for Progress {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Progress::Unsolved => "unsolved",
+            Progress::Started => "in progress",
+            Progress::Solved => "solved",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// A file of puzzles, one on each line in the common one-line format
+///
+/// The progress and the saved game of each puzzle are kept in files next to the collection
+pub struct Collection {
+    /// The file of the collection
+    file: PathBuf,
+    /// The puzzles, in the order of the file
+    puzzles: Vec<Board>,
+    /// The progress of each puzzle
+    progress: Vec<Progress>,
+}
+
+impl Collection {
+    /// Reads a collection, and the progress of its puzzles if any is saved
+    ///
+    /// Empty lines, and lines starting with '#', are skipped
+    ///
+    /// ## Arguments
+    ///
+    /// * file - The file to read from
+    ///
+    /// ## Returns
+    ///
+    /// the collection, or the error if the file can not be read or a line is not a puzzle
+    pub fn load(file: &str) -> io::Result<Self> {
+        let puzzles = Self::read(&std::fs::read_to_string(file)?)?;
+        let mut collection = Self {
+            file: PathBuf::from(file),
+            progress: vec![Progress::Unsolved; puzzles.len()],
+            puzzles,
+        };
+        if let Ok(progress) = std::fs::read_to_string(collection.progress_file()) {
+            collection.read_progress(&progress);
+        }
+        Ok(collection)
+    }
+
+    /// Used to read the puzzles of a collection
+    ///
+    /// ## Returns
+    ///
+    /// the puzzles, or the error of the first line which is not a puzzle
+    fn read(string: &str) -> io::Result<Vec<Board>> {
+        string
+            .lines()
+            .enumerate()
+            .map(|(number, line)| (number, line.trim()))
+            .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+            .map(|(number, line)| {
+                Board::parse_standard(line).map_err(|err| {
+                    io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("line {}: {}", number + 1, err),
+                    )
+                })
+            })
+            .collect()
+    }
+
+    /// Gets the number of puzzles
+    pub fn len(&self) -> usize {
+        self.puzzles.len()
+    }
+
+    /// Gets a puzzle, None if there is no puzzle at the index
+    pub fn puzzle(&self, index: usize) -> Option<&Board> {
+        self.puzzles.get(index)
+    }
+
+    /// Gets the progress of a puzzle
+    pub fn progress(&self, index: usize) -> Progress {
+        self.progress
+            .get(index)
+            .copied()
+            .unwrap_or(Progress::Unsolved)
+    }
+
+    /// Sets the progress of a puzzle, and saves the progress of the collection
+    ///
+    /// ## Arguments
+    ///
+    /// * index - The index of the puzzle
+    /// * progress - The new progress
+    pub fn set_progress(&mut self, index: usize, progress: Progress) -> io::Result<()> {
+        if let Some(each) = self.progress.get_mut(index) {
+            *each = progress;
+        }
+        std::fs::write(self.progress_file(), self.progress_to_string())
+    }
+
+    /// Gets the file the game of a puzzle is saved to, next to the collection
+    pub fn save_file(&self, index: usize) -> String {
+        let stem = self.file.file_stem().unwrap_or_default().to_string_lossy();
+        self.file
+            .with_file_name(format!("{}-{}.save", stem, index + 1))
+            .to_string_lossy()
+            .to_string()
+    }
+
+    /// Gets the file the progress of the collection is saved to, next to the collection
+    fn progress_file(&self) -> PathBuf {
+        self.file.with_extension("progress")
+    }
+
+    /// Used to convert the progress to parseable lines
+    ///
+    /// ## Returns
+    ///
+    /// a line of "number progress" for each puzzle which is not unsolved, counting from 1
+    fn progress_to_string(&self) -> String {
+        self.progress
+            .iter()
+            .enumerate()
+            .filter(|(_, progress)| **progress != Progress::Unsolved)
+            .map(|(index, progress)| format!("{} {}\n", index + 1, progress))
+            .collect()
+    }
+
+    /// Used to read the progress written by progress_to_string, unknown lines are skipped
+    fn read_progress(&mut self, string: &str) {
+        for line in string.lines() {
+            let Some((number, name)) = line.split_once(' ') else {
+                continue;
+            };
+            let progress = match name {
+                "in progress" => Progress::Started,
+                "solved" => Progress::Solved,
+                _ => continue,
+            };
+            if let Some(each) = number
+                .parse::<usize>()
+                .ok()
+                .and_then(|number| self.progress.get_mut(number.checked_sub(1)?))
+            {
+                *each = progress;
+            }
+        }
+    }
+}
+
+/// Used to check the extension of a collection
+///
+/// ## Arguments
+///
+/// * path - The path of the file
+///
+/// ## Returns
+///
+/// a boolean if the file is a collection
+pub fn is_collection(path: &str) -> bool {
+    Path::new(path)
+        .extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("sdm"))
+}
+
+#[cfg(test)]
+mod collection_test {
+    use super::*;
+
+    const COLLECTION: &str = "# Two puzzles
+53..7....6..195....98....6.8...6...34..8.3..17...2...6.6....28....419..5....8..79
+
+4.....8.5.3..........7......2.....6.....8.4......1.......6.3.7.5..2.....1.4......
+";
+
+    fn get_collection() -> Collection {
+        let puzzles = Collection::read(COLLECTION).expect("The collection is valid");
+        Collection {
+            file: PathBuf::from("puzzles/classic.sdm"),
+            progress: vec![Progress::Unsolved; puzzles.len()],
+            puzzles,
+        }
+    }
+
+    #[test]
+    fn test_read() {
+        let collection = get_collection();
+
+        assert_eq!(collection.len(), 2);
+        assert_eq!(
+            collection.puzzle(1).map(Board::to_standard),
+            Some(String::from(
+                "4.....8.5.3..........7......2.....6.....8.4......1.......6.3.7.5..2.....1.4......"
+            ))
+        );
+        assert!(collection.puzzle(2).is_none());
+
+        let error = Collection::read(&COLLECTION.replacen('5', "x", 1)).err();
+
+        assert_eq!(
+            error.map(|error| error.to_string()),
+            Some(String::from("line 2: invalid character 'x' at position 0"))
+        );
+    }
+
+    #[test]
+    fn test_progress() {
+        let mut collection = get_collection();
+        collection.progress[1] = Progress::Solved;
+
+        assert_eq!(collection.progress_to_string(), "2 solved\n");
+
+        let mut read = get_collection();
+        read.read_progress("1 in progress\n2 solved\n3 solved\n0 solved\n");
+
+        assert_eq!(read.progress(0), Progress::Started);
+        assert_eq!(read.progress(1), Progress::Solved);
+        assert_eq!(read.progress(2), Progress::Unsolved);
+    }
+
+    #[test]
+    fn test_files() {
+        let collection = get_collection();
+
+        assert_eq!(collection.save_file(0), "puzzles/classic-1.save");
+        assert_eq!(
+            collection.progress_file(),
+            PathBuf::from("puzzles/classic.progress")
+        );
+        assert!(is_collection("puzzles/classic.SDM"));
+        assert!(!is_collection("puzzles/classic.sdk"));
+    }
+}
@@ -0,0 +1,158 @@
+use std::{collections::BTreeMap, time::Duration};
+
+use chrono::{Datelike, Local, NaiveDate};
+
+use crate::board::difficulties::Difficulties;
+
+/// The file the times of the daily puzzles are kept in
+pub const DAILY_FILE: &str = "daily";
+
+/// The file the daily puzzle in progress is saved to
+pub const DAILY_SAVE: &str = "save-daily";
+
+/// The difficulty of the daily puzzle, the same for everyone so the puzzle is too
+pub const DAILY_DIFFICULTY: Difficulties = Difficulties::Medium;
+
+/// Gets the local date of today
+pub fn today() -> NaiveDate {
+    Local::now().date_naive()
+}
+
+/// Gets the seed of the daily puzzle of a date
+///
+/// ## Arguments
+///
+/// * date - The date to get the seed of
+///
+/// ## Returns
+///
+/// the date as the number YYYYMMDD, so it can easily be shared
+pub fn seed(date: NaiveDate) -> u64 {
+    date.year() as u64 * 10000 + date.month() as u64 * 100 + date.day() as u64
+}
+
+/// The times the daily puzzles were done in, for each day
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Daily {
+    times: BTreeMap<NaiveDate, Duration>,
+}
+
+impl Daily {
+    /// Reads the times from a file
+    ///
+    /// ## Arguments
+    ///
+    /// * file - The file to read from
+    ///
+    /// ## Returns
+    ///
+    /// the times, empty if the file can not be read
+    pub fn load(file: &str) -> Self {
+        let mut daily = Self::default();
+        if let Ok(string) = std::fs::read_to_string(file) {
+            for line in string.lines() {
+                daily.read_line(line);
+            }
+        }
+        daily
+    }
+
+    /// Writes the times to a file
+    ///
+    /// ## Arguments
+    ///
+    /// * file - The file to write to
+    pub fn save(&self, file: &str) -> std::io::Result<()> {
+        std::fs::write(file, self.to_string())
+    }
+
+    /// Gets the time the puzzle of a date was done in, None if it is not done
+    pub fn time(&self, date: NaiveDate) -> Option<Duration> {
+        self.times.get(&date).copied()
+    }
+
+    /// Counts the puzzle of a date as done, keeping the best time if it was done before
+    ///
+    /// ## Arguments
+    ///
+    /// * date - The date of the puzzle
+    /// * time - The time it was done in
+    pub fn complete(&mut self, date: NaiveDate, time: Duration) {
+        let best = self.times.entry(date).or_insert(time);
+        *best = time.min(*best);
+    }
+
+    /// Used to read a line written by Display, lines which are not a day are skipped
+    fn read_line(&mut self, line: &str) {
+        if let Some((date, seconds)) = line.split_once(' ') {
+            if let (Ok(date), Ok(seconds)) = (date.parse(), seconds.parse()) {
+                self.times.insert(date, Duration::from_secs(seconds));
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for Daily {
+    /// Used to convert the times to parseable lines
+    ///
+    /// ## Returns
+    ///
+    /// a line of "date seconds" for each day done, the date as YYYY-MM-DD
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (date, time) in self.times.iter() {
+            writeln!(f, "{} {}", date, time.as_secs())?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod daily_test {
+    use super::*;
+
+    fn get_date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2022, 7, day).expect("A valid date")
+    }
+
+    #[test]
+    fn test_seed() {
+        assert_eq!(seed(get_date(9)), 20220709);
+        assert_ne!(seed(get_date(9)), seed(get_date(10)));
+    }
+
+    #[test]
+    fn test_complete() {
+        let mut daily = Daily::default();
+
+        assert_eq!(daily.time(get_date(9)), None);
+
+        daily.complete(get_date(9), Duration::from_secs(300));
+        daily.complete(get_date(9), Duration::from_secs(400));
+
+        assert_eq!(daily.time(get_date(9)), Some(Duration::from_secs(300)));
+
+        daily.complete(get_date(9), Duration::from_secs(200));
+
+        assert_eq!(daily.time(get_date(9)), Some(Duration::from_secs(200)));
+        assert_eq!(daily.time(get_date(10)), None);
+    }
+
+    #[test]
+    fn test_to_string() {
+        let mut daily = Daily::default();
+        daily.complete(get_date(10), Duration::from_secs(90));
+        daily.complete(get_date(9), Duration::from_secs(300));
+
+        let string = daily.to_string();
+
+        assert_eq!(string, "2022-07-09 300\n2022-07-10 90\n");
+
+        let mut read = Daily::default();
+        for line in string.lines() {
+            read.read_line(line);
+        }
+        read.read_line("2022-13-01 60");
+
+        assert_eq!(read, daily);
+    }
+}
@@ -0,0 +1,240 @@
+use serde::{Deserialize, Serialize};
+
+use crate::board::size::Size;
+
+/// A single change to the board, which can be undone and redone
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Move {
+    /// The row of the changed position
+    pub row: usize,
+    /// The column of the changed position
+    pub column: usize,
+    /// The number before the change
+    pub old: Option<usize>,
+    /// The number after the change
+    pub new: Option<usize>,
+    /// The notes changed, as (row, column, old notes, new notes)
+    pub notes: Vec<(usize, usize, u16, u16)>,
+}
+
+/// Used to read an optional number, with '.' for None
+fn number_from_str(string: &str) -> Option<Option<usize>> {
+    if string == "." {
+        Some(None)
+    } else {
+        string.parse().ok().map(Some)
+    }
+}
+
+impl Move {
+    /// Used to read a move from the line format of older saves
+    ///
+    /// The string is "row column old new notes", where a missing number is a '.', and the notes
+    /// are "row:column:old:new" separated by ',' or '-' if there are none
+    ///
+    /// ## Returns
+    ///
+    /// the move, or None if the string is not a move
+    fn from_str(string: &str) -> Option<Self> {
+        let mut parts = string.split(' ');
+        let row = parts.next()?.parse().ok()?;
+        let column = parts.next()?.parse().ok()?;
+        let old = number_from_str(parts.next()?)?;
+        let new = number_from_str(parts.next()?)?;
+        let notes = match parts.next()? {
+            "-" => vec![],
+            notes => notes
+                .split(',')
+                .map(|each| {
+                    let mut values = each.split(':');
+                    Some((
+                        values.next()?.parse().ok()?,
+                        values.next()?.parse().ok()?,
+                        values.next()?.parse().ok()?,
+                        values.next()?.parse().ok()?,
+                    ))
+                })
+                .collect::<Option<Vec<(usize, usize, u16, u16)>>>()?,
+        };
+        Some(Self {
+            row,
+            column,
+            old,
+            new,
+            notes,
+        })
+    }
+
+    /// Tests if the move only changes positions, numbers and notes a board of a size has
+    pub fn fits(&self, size: Size) -> bool {
+        let side = size.side();
+        let on_board = |row: usize, column: usize| row < side && column < side;
+        on_board(self.row, self.column)
+            && self
+                .old
+                .iter()
+                .chain(self.new.iter())
+                .all(|value| *value < side)
+            && self.notes.iter().all(|(row, column, old, new)| {
+                on_board(*row, *column) && (old | new) & !size.all() == 0
+            })
+    }
+}
+
+/// The moves made, used to undo and redo them
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct History {
+    /// The moves which can be undone, the last one made last
+    done: Vec<Move>,
+    /// The moves which can be redone, the last one undone last
+    undone: Vec<Move>,
+}
+
+impl History {
+    /// Adds a move, which removes the moves which could be redone
+    pub fn push(&mut self, change: Move) {
+        self.done.push(change);
+        self.undone.clear();
+    }
+
+    /// Takes the last move made, to undo it
+    pub fn undo(&mut self) -> Option<Move> {
+        let change = self.done.pop()?;
+        self.undone.push(change.clone());
+        Some(change)
+    }
+
+    /// Takes the last move undone, to make it again
+    pub fn redo(&mut self) -> Option<Move> {
+        let change = self.undone.pop()?;
+        self.done.push(change.clone());
+        Some(change)
+    }
+
+    /// Tests if every move, made or undone, fits a board of a size
+    pub fn fits(&self, size: Size) -> bool {
+        self.done
+            .iter()
+            .chain(self.undone.iter())
+            .all(|change| change.fits(size))
+    }
+
+    /// Used to read a line of the line format of older saves, which starts with "done" or
+    /// "undone" and then the move
+    ///
+    /// ## Returns
+    ///
+    /// None if the line is not of the history, else a boolean if it was a move
+    pub fn read_line(&mut self, line: &str) -> Option<bool> {
+        let (stack, change) = match line.split_once(' ') {
+            Some(("done", change)) => (&mut self.done, change),
+            Some(("undone", change)) => (&mut self.undone, change),
+            _ => return None,
+        };
+        match Move::from_str(change) {
+            Some(change) => {
+                stack.push(change);
+                Some(true)
+            }
+            None => Some(false),
+        }
+    }
+}
+
+#[cfg(test)]
+mod history_test {
+    use super::*;
+
+    /// Gets a move placing a 3 in the middle, which removed it from a note
+    fn get_move() -> Move {
+        Move {
+            row: 4,
+            column: 4,
+            old: None,
+            new: Some(2),
+            notes: vec![(4, 5, 0b101, 0b001)],
+        }
+    }
+
+    #[test]
+    fn test_undo_redo() {
+        let mut history = History::default();
+
+        assert_eq!(history.undo(), None);
+
+        history.push(get_move());
+
+        assert_eq!(history.redo(), None);
+        assert_eq!(history.undo(), Some(get_move()));
+        assert_eq!(history.undo(), None);
+        assert_eq!(history.redo(), Some(get_move()));
+        assert_eq!(history.undo(), Some(get_move()));
+
+        history.push(get_move());
+
+        assert_eq!(history.redo(), None);
+    }
+
+    #[test]
+    fn test_read_line() {
+        let mut history = History::default();
+        history.push(get_move());
+        history.push(Move {
+            row: 0,
+            column: 8,
+            old: Some(1),
+            new: None,
+            notes: vec![],
+        });
+        history.undo();
+
+        let mut read = History::default();
+
+        assert_eq!(read.read_line("done 4 4 . 2 4:5:5:1"), Some(true));
+        assert_eq!(read.read_line("undone 0 8 1 . -"), Some(true));
+        assert_eq!(read, history);
+        assert_eq!(read.read_line("done 4 4 x 2 -"), Some(false));
+        assert_eq!(read.read_line("done 4 4 . 2 4:5:70000:1"), Some(false));
+        assert_eq!(read.read_line("notes 0,0,0"), None);
+    }
+
+    #[test]
+    fn test_fits() {
+        let mut history = History::default();
+        history.push(get_move());
+
+        assert!(history.fits(Size::default()));
+        assert!(!history.fits(Size::new(4).expect("4 is a size")));
+
+        let changes = [
+            Move {
+                new: Some(9),
+                ..get_move()
+            },
+            Move {
+                old: Some(16),
+                ..get_move()
+            },
+            Move {
+                column: 9,
+                ..get_move()
+            },
+            Move {
+                notes: vec![(4, 9, 0, 1)],
+                ..get_move()
+            },
+            Move {
+                notes: vec![(4, 5, 1 << 9, 0)],
+                ..get_move()
+            },
+        ];
+        for change in changes {
+            let mut history = History::default();
+            history.push(get_move());
+            history.push(change);
+            history.undo();
+
+            assert!(!history.fits(Size::default()));
+        }
+    }
+}
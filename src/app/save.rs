@@ -0,0 +1,145 @@
+use std::io;
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+use super::history::History;
+use crate::board::variant::Variant;
+
+/// The version of the save format written, raised whenever a change can not be read by older
+/// versions of the game
+pub const SAVE_VERSION: u32 = 2;
+
+/// Everything about a game, as it is written to a save file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Save {
+    /// The version of the format the save was written in
+    pub version: u32,
+    /// The board, in the format of Display for Board
+    pub board: String,
+    /// The rules the board is played by, the classic ones in saves from before there were others
+    #[serde(default)]
+    pub variant: Variant,
+    /// The notes of every position as bitflags, row by row
+    pub notes: Vec<Vec<u16>>,
+    /// The active row
+    pub row: usize,
+    /// The active column
+    pub column: usize,
+    /// If digits toggle notes instead of entering numbers
+    pub note_mode: bool,
+    /// The time spent playing, in seconds
+    pub time: u64,
+    /// The difficulty, as its number
+    pub difficulty: u8,
+    /// The seed the board was created from, if it is known
+    pub seed: Option<u64>,
+    /// The date, if it is a daily puzzle
+    pub day: Option<NaiveDate>,
+    /// The moves made, to undo and redo
+    pub history: History,
+}
+
+impl Save {
+    /// Used to read a save written by to_string
+    ///
+    /// ## Arguments
+    ///
+    /// * string - The contents of the save file
+    ///
+    /// ## Returns
+    ///
+    /// the save, or the error if it is not a save or is written by a newer version of the game
+    pub fn parse(string: &str) -> io::Result<Self> {
+        let save: Self = serde_json::from_str(string)?;
+        if save.version > SAVE_VERSION {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "the save is version {}, but only up to version {} can be read",
+                    save.version, SAVE_VERSION
+                ),
+            ));
+        }
+        Ok(save)
+    }
+
+    /// Used to check if a save file is written in this format, and not the older line format
+    pub fn is_save(string: &str) -> bool {
+        string.trim_start().starts_with('{')
+    }
+}
+
+impl std::fmt::Display for Save {
+    /// Used to convert the save to readable JSON
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let json = serde_json::to_string_pretty(self).map_err(|_| std::fmt::Error)?;
+        writeln!(f, "{}", json)
+    }
+}
+
+#[cfg(test)]
+mod save_test {
+    use super::*;
+    use crate::app::history::Move;
+
+    fn get_save() -> Save {
+        let mut history = History::default();
+        history.push(Move {
+            row: 0,
+            column: 2,
+            old: None,
+            new: Some(3),
+            notes: vec![(0, 3, 0b1000, 0)],
+        });
+        Save {
+            version: SAVE_VERSION,
+            board: String::from(
+                "ec3.g....f..aie....ih....f.h...f...cd..h.c..ag...b...f.f....bh....dai..e....h..gi",
+            ),
+            variant: Variant::X,
+            notes: vec![vec![0; 9]; 9],
+            row: 4,
+            column: 5,
+            note_mode: true,
+            time: 125,
+            difficulty: 2,
+            seed: Some(42),
+            day: NaiveDate::from_ymd_opt(2022, 7, 9),
+            history,
+        }
+    }
+
+    #[test]
+    fn test_to_string() {
+        let save = get_save();
+        let string = save.to_string();
+
+        assert!(Save::is_save(&string));
+        assert!(string.contains("\"day\": \"2022-07-09\""));
+        assert!(string.contains("\"variant\": \"x\""));
+        assert_eq!(Save::parse(&string).ok(), Some(save));
+        assert!(!Save::is_save("ec3.g....f..aie"));
+    }
+
+    #[test]
+    fn test_version() {
+        let mut save = get_save();
+        save.version = SAVE_VERSION + 1;
+
+        assert!(Save::parse(&save.to_string()).is_err());
+        assert!(Save::parse("{\"version\": 1}").is_err());
+    }
+
+    #[test]
+    fn test_no_variant() {
+        let save = get_save();
+        let mut json = serde_json::to_value(&save).expect("The save is JSON");
+        json.as_object_mut().map(|save| save.remove("variant"));
+        json["version"] = 1.into();
+
+        let save = Save::parse(&json.to_string()).expect("Saves without a variant can be read");
+
+        assert_eq!(save.variant, Variant::Classic);
+    }
+}
@@ -1,5 +1,21 @@
-#[derive(PartialEq)]
+/// The screens the app can show, and which keys are read
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum States {
+    /// The main menu, to start, load or continue a game
+    Menu,
+    /// Picking the difficulty of a new game
     New,
+    /// Picking the difficulty of the saved game to load
+    Load,
+    /// Picking a puzzle of the collection to play
+    Puzzles,
+    /// Playing a game
+    Playing,
+    /// The game is paused, and the board is hidden
+    Paused,
+    /// The game is won
+    Won,
+    /// The statistics of all games played
+    Stats,
     Quit
 }
@@ -0,0 +1,419 @@
+use std::time::Duration;
+
+use super::timer::format_time;
+use crate::board::{difficulties::Difficulties, size::Size, variant::Variant};
+
+/// The file the statistics are kept in
+pub const STATS_FILE: &str = "stats";
+
+/// The number of difficulties kept apart
+const LEVELS: usize = 3;
+
+/// The statistics of the games played at a single difficulty
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Record {
+    /// The number of new games started
+    pub started: u32,
+    /// The number of games won
+    pub won: u32,
+    /// The time spent on all the games won
+    pub total: Duration,
+    /// The fastest time a game was won in
+    pub best: Option<Duration>,
+    /// The number of games won in a row
+    pub streak: u32,
+    /// The most games ever won in a row
+    pub best_streak: u32,
+    /// If the last game started has not been won yet
+    playing: bool,
+}
+
+impl Record {
+    /// Gets the average time of the games won, None if no game is won
+    pub fn average(&self) -> Option<Duration> {
+        if self.won == 0 {
+            None
+        } else {
+            Some(self.total / self.won)
+        }
+    }
+
+    /// Used to create a record from the string written by Display
+    ///
+    /// ## Returns
+    ///
+    /// the record, or None if the string is not a record
+    fn from_str(string: &str) -> Option<Self> {
+        let mut parts = string.split(' ').map(|part| part.parse::<u64>().ok());
+        let mut next = || parts.next().flatten();
+        let started = next()? as u32;
+        let won = next()? as u32;
+        let total = Duration::from_secs(next()?);
+        // A missing best time is written as '.', which does not parse
+        let best = next().map(Duration::from_secs);
+        Some(Self {
+            started,
+            won,
+            total,
+            best,
+            streak: next()? as u32,
+            best_streak: next()? as u32,
+            playing: next()? == 1,
+        })
+    }
+}
+
+impl std::fmt::Display for Record {
+    /// Used to convert the record to a parseable string
+    ///
+    /// ## Returns
+    ///
+    /// a string of "started won total best streak best_streak playing", with the times in
+    /// seconds and a '.' for a missing best time
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} {} {} {} {} {} {}",
+            self.started,
+            self.won,
+            self.total.as_secs(),
+            self.best
+                .map_or(String::from("."), |best| best.as_secs().to_string()),
+            self.streak,
+            self.best_streak,
+            u8::from(self.playing)
+        )
+    }
+}
+
+/// The statistics of all games played, kept apart for each size, variant and difficulty
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Stats {
+    /// The records of each size and variant played, indexed by difficulty
+    records: Vec<(Size, Variant, [Record; LEVELS])>,
+}
+
+impl Stats {
+    /// Reads the statistics from a file
+    ///
+    /// ## Arguments
+    ///
+    /// * file - The file to read from
+    ///
+    /// ## Returns
+    ///
+    /// the statistics, empty if the file can not be read
+    pub fn load(file: &str) -> Self {
+        let mut stats = Self::default();
+        if let Ok(string) = std::fs::read_to_string(file) {
+            for line in string.lines() {
+                stats.read_line(line);
+            }
+        }
+        stats
+    }
+
+    /// Writes the statistics to a file
+    ///
+    /// ## Arguments
+    ///
+    /// * file - The file to write to
+    pub fn save(&self, file: &str) -> std::io::Result<()> {
+        std::fs::write(file, self.to_string())
+    }
+
+    /// Gets the record of a difficulty, on a size and variant
+    pub fn record(&self, difficulty: &Difficulties, size: Size, variant: Variant) -> Record {
+        self.records
+            .iter()
+            .find(|(s, v, _)| *s == size && *v == variant)
+            .map_or(Record::default(), |(_, _, records)| {
+                records[difficulty.to_num() as usize].clone()
+            })
+    }
+
+    /// Gets the record of a difficulty to change, on a size and variant
+    fn record_mut(
+        &mut self,
+        difficulty: &Difficulties,
+        size: Size,
+        variant: Variant,
+    ) -> &mut Record {
+        let index = match self
+            .records
+            .iter()
+            .position(|(s, v, _)| *s == size && *v == variant)
+        {
+            Some(index) => index,
+            None => {
+                self.records.push((size, variant, Default::default()));
+                self.records.len() - 1
+            }
+        };
+        &mut self.records[index].2[difficulty.to_num() as usize]
+    }
+
+    /// Counts a new game, which ends the streak if the last game was never won
+    ///
+    /// ## Arguments
+    ///
+    /// * difficulty - The difficulty of the new game
+    /// * size - The size of the new game
+    /// * variant - The variant of the new game
+    pub fn start(&mut self, difficulty: &Difficulties, size: Size, variant: Variant) {
+        let record = self.record_mut(difficulty, size, variant);
+        record.started += 1;
+        if record.playing {
+            record.streak = 0;
+        }
+        record.playing = true;
+    }
+
+    /// Counts a game won
+    ///
+    /// ## Arguments
+    ///
+    /// * difficulty - The difficulty of the game
+    /// * size - The size of the game
+    /// * variant - The variant of the game
+    /// * time - The time it took to win
+    ///
+    /// ## Returns
+    ///
+    /// a boolean if the time is the best yet
+    pub fn win(
+        &mut self,
+        difficulty: &Difficulties,
+        size: Size,
+        variant: Variant,
+        time: Duration,
+    ) -> bool {
+        let record = self.record_mut(difficulty, size, variant);
+        record.won += 1;
+        record.total += time;
+        record.streak += 1;
+        record.best_streak = record.best_streak.max(record.streak);
+        record.playing = false;
+        let best = record.best.is_none_or(|best| time < best);
+        if best {
+            record.best = Some(time);
+        }
+        best
+    }
+
+    /// Gets the sizes and variants with records, the classic game always first
+    fn games(&self) -> Vec<(Size, Variant)> {
+        let classic = (Size::default(), Variant::default());
+        let mut games = vec![classic];
+        games.extend(
+            self.records
+                .iter()
+                .map(|(size, variant, _)| (*size, *variant))
+                .filter(|game| *game != classic),
+        );
+        games
+    }
+
+    /// Used to get a line for each difficulty of each game played, to show the statistics
+    pub fn lines(&self) -> Vec<String> {
+        self.games()
+            .into_iter()
+            .flat_map(|(size, variant)| {
+                (0..LEVELS as u8).map(move |level| (Difficulties::from_num(level), size, variant))
+            })
+            .map(|(difficulty, size, variant)| {
+                let record = self.record(&difficulty, size, variant);
+                let mut name = difficulty.to_string();
+                if (size, variant) != (Size::default(), Variant::default()) {
+                    name.push_str(&format!(" {} {}", size, variant.name()));
+                }
+                format!(
+                    "{}: won {} of {}, best {}, average {}, streak {} (best {})",
+                    name,
+                    record.won,
+                    record.started,
+                    record.best.map_or(String::from("-"), format_time),
+                    record.average().map_or(String::from("-"), format_time),
+                    record.streak,
+                    record.best_streak
+                )
+            })
+            .collect()
+    }
+
+    /// Used to read a line written by Display, lines of unknown games are skipped
+    ///
+    /// Lines naming only the difficulty are of the classic 9x9 game, which is how the files
+    /// written before the other sizes and variants were kept apart are read
+    fn read_line(&mut self, line: &str) {
+        if let Some((name, record)) = line.split_once(' ') {
+            if let (Some((difficulty, size, variant)), Some(record)) =
+                (read_name(name), Record::from_str(record))
+            {
+                *self.record_mut(&difficulty, size, variant) = record;
+            }
+        }
+    }
+}
+
+/// Gets the name a record is written with, the difficulty followed by the side and the variant
+/// when they are not the classic ones
+fn write_name(difficulty: &Difficulties, size: Size, variant: Variant) -> String {
+    let mut name = difficulty.to_string();
+    if size != Size::default() {
+        name.push_str(&format!("-{}", size.side()));
+    }
+    if variant != Variant::default() {
+        name.push_str(&format!("-{}", variant));
+    }
+    name
+}
+
+/// Used to read a name written by write_name
+///
+/// ## Returns
+///
+/// the difficulty, size and variant, or None if the name is not of a known game
+fn read_name(name: &str) -> Option<(Difficulties, Size, Variant)> {
+    let mut parts = name.split('-');
+    let first = parts.next()?;
+    let difficulty = (0..LEVELS as u8)
+        .map(Difficulties::from_num)
+        .find(|difficulty| difficulty.to_string() == first)?;
+    let mut size = Size::default();
+    let mut variant = Variant::default();
+    for part in parts {
+        match (part.parse::<Size>(), part.parse::<Variant>()) {
+            (Ok(read), _) => size = read,
+            (_, Ok(read)) => variant = read,
+            _ => return None,
+        }
+    }
+    Some((difficulty, size, variant))
+}
+
+impl std::fmt::Display for Stats {
+    /// Used to convert the statistics to parseable lines
+    ///
+    /// ## Returns
+    ///
+    /// a line for each difficulty of each game played, starting with its name
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (size, variant) in self.games() {
+            for level in 0..LEVELS as u8 {
+                let difficulty = Difficulties::from_num(level);
+                let record = self.record(&difficulty, size, variant);
+                writeln!(f, "{} {}", write_name(&difficulty, size, variant), record)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod stats_test {
+    use super::*;
+
+    #[test]
+    fn test_win() {
+        let mut stats = Stats::default();
+        let (size, variant) = (Size::default(), Variant::default());
+
+        stats.start(&Difficulties::Easy, size, variant);
+
+        assert!(stats.win(&Difficulties::Easy, size, variant, Duration::from_secs(100)));
+
+        stats.start(&Difficulties::Easy, size, variant);
+
+        assert!(!stats.win(&Difficulties::Easy, size, variant, Duration::from_secs(200)));
+
+        let record = stats.record(&Difficulties::Easy, size, variant);
+
+        assert_eq!(record.started, 2);
+        assert_eq!(record.won, 2);
+        assert_eq!(record.best, Some(Duration::from_secs(100)));
+        assert_eq!(record.average(), Some(Duration::from_secs(150)));
+        assert_eq!(record.streak, 2);
+        assert_eq!(
+            stats.record(&Difficulties::Hard, size, variant),
+            Record::default()
+        );
+    }
+
+    #[test]
+    fn test_streak() {
+        let mut stats = Stats::default();
+        let (size, variant) = (Size::default(), Variant::default());
+        let minute = Duration::from_secs(60);
+
+        stats.start(&Difficulties::Medium, size, variant);
+        stats.win(&Difficulties::Medium, size, variant, minute);
+        stats.start(&Difficulties::Medium, size, variant);
+        stats.win(&Difficulties::Medium, size, variant, minute);
+        stats.start(&Difficulties::Medium, size, variant);
+        stats.start(&Difficulties::Medium, size, variant);
+
+        let record = stats.record(&Difficulties::Medium, size, variant);
+
+        assert_eq!(record.streak, 0);
+        assert_eq!(record.best_streak, 2);
+        assert_eq!(record.average(), Some(Duration::from_secs(60)));
+    }
+
+    #[test]
+    fn test_to_string() {
+        let mut stats = Stats::default();
+        let (size, variant) = (Size::default(), Variant::default());
+        stats.start(&Difficulties::Hard, size, variant);
+        stats.win(&Difficulties::Hard, size, variant, Duration::from_secs(300));
+        stats.start(&Difficulties::Hard, size, variant);
+
+        let string = stats.to_string();
+
+        assert_eq!(
+            string,
+            "easy 0 0 0 . 0 0 0\nmedium 0 0 0 . 0 0 0\nhard 2 1 300 300 1 1 1\n"
+        );
+
+        let mut read = Stats::default();
+        for line in string.lines() {
+            read.read_line(line);
+        }
+        read.read_line("expert 1 1 1 1 1 1 1");
+
+        assert_eq!(read, stats);
+    }
+
+    #[test]
+    fn test_games() {
+        let mut stats = Stats::default();
+        let (size, variant) = (Size::new(12).expect("12 is a size"), Variant::default());
+        stats.start(&Difficulties::Easy, size, Variant::X);
+        stats.win(
+            &Difficulties::Easy,
+            size,
+            Variant::X,
+            Duration::from_secs(600),
+        );
+
+        let classic = stats.record(&Difficulties::Easy, Size::default(), Variant::default());
+
+        assert_eq!(classic, Record::default());
+        assert_eq!(stats.record(&Difficulties::Easy, size, Variant::X).won, 1);
+        assert_eq!(stats.record(&Difficulties::Easy, size, variant).won, 0);
+
+        let string = stats.to_string();
+
+        assert!(string.contains("\neasy-12-x 1 1 600 600 1 1 0\n"));
+        assert_eq!(stats.lines().len(), 6);
+        assert!(stats.lines()[3].starts_with("easy 12x12 Sudoku-X: won 1 of 1"));
+
+        let mut read = Stats::default();
+        for line in string.lines() {
+            read.read_line(line);
+        }
+        read.read_line("easy-12-y 1 1 1 1 1 1 1");
+
+        assert_eq!(read.to_string(), string);
+    }
+}
@@ -0,0 +1,110 @@
+use std::time::{Duration, Instant};
+
+/// Keeps track of the time spent playing, not counting the time paused
+#[derive(Debug, Clone)]
+pub struct Timer {
+    /// The time counted before the last start
+    before: Duration,
+    /// When the timer was last started, None if it is paused
+    started: Option<Instant>,
+}
+
+impl Timer {
+    /// Creates a running timer
+    ///
+    /// ## Arguments
+    ///
+    /// * elapsed - The time already counted
+    pub fn new(elapsed: Duration) -> Self {
+        Self {
+            before: elapsed,
+            started: Some(Instant::now()),
+        }
+    }
+
+    /// Gets the time counted
+    pub fn elapsed(&self) -> Duration {
+        self.before
+            + self
+                .started
+                .map_or(Duration::ZERO, |started| started.elapsed())
+    }
+
+    /// Tests if the timer is paused
+    pub fn is_paused(&self) -> bool {
+        self.started.is_none()
+    }
+
+    /// Stops counting time
+    pub fn pause(&mut self) {
+        self.before = self.elapsed();
+        self.started = None;
+    }
+
+    /// Starts counting time again
+    pub fn resume(&mut self) {
+        if self.is_paused() {
+            self.started = Some(Instant::now());
+        }
+    }
+}
+
+/// Used to show a time as minutes and seconds, with hours if there are any
+///
+/// ## Arguments
+///
+/// * time - The time to show
+pub fn format_time(time: Duration) -> String {
+    let seconds = time.as_secs();
+    let (hours, minutes, seconds) = (seconds / 3600, (seconds / 60) % 60, seconds % 60);
+    if hours > 0 {
+        format!("{}:{:02}:{:02}", hours, minutes, seconds)
+    } else {
+        format!("{:02}:{:02}", minutes, seconds)
+    }
+}
+
+impl std::fmt::Display for Timer {
+    /// Used to show the time counted as minutes and seconds, with hours if there are any
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", format_time(self.elapsed()))
+    }
+}
+
+#[cfg(test)]
+mod timer_test {
+    use super::*;
+
+    #[test]
+    fn test_pause() {
+        let mut timer = Timer::new(Duration::from_secs(10));
+
+        assert!(!timer.is_paused());
+        assert!(timer.elapsed() >= Duration::from_secs(10));
+
+        timer.pause();
+        let paused = timer.elapsed();
+        std::thread::sleep(Duration::from_millis(5));
+
+        assert!(timer.is_paused());
+        assert_eq!(timer.elapsed(), paused);
+
+        timer.resume();
+        std::thread::sleep(Duration::from_millis(5));
+
+        assert!(timer.elapsed() > paused);
+    }
+
+    #[test]
+    fn test_to_string() {
+        let mut timer = Timer::new(Duration::from_secs(65));
+        timer.pause();
+
+        assert_eq!(timer.to_string(), "01:05");
+
+        let mut timer = Timer::new(Duration::from_secs(3 * 3600 + 7));
+        timer.pause();
+
+        assert_eq!(timer.to_string(), "3:00:07");
+    }
+}
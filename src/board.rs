@@ -1,151 +1,459 @@
+pub mod constraint;
 pub mod difficulties;
+pub mod formats;
+pub mod parse_error;
+pub mod size;
+pub mod variant;
+
+use std::rc::Rc;
+
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+
+use self::{
+    constraint::{Constraint, Unit},
+    difficulties::Difficulties,
+    parse_error::ParseError,
+    size::Size,
+    variant::Variant,
+};
+use crate::solver::rating::Rating;
+
+/// The symbols the values are shown as, the digits and then letters for the values above nine
+const SYMBOLS: [char; 16] = [
+    '1', '2', '3', '4', '5', '6', '7', '8', '9', 'A', 'B', 'C', 'D', 'E', 'F', 'G',
+];
+
+/// Gets the symbol a value is shown as, 1-9 and then A-G
+///
+/// ## Arguments
+///
+/// * value - The value, with 0 being '1'
+pub fn symbol(value: usize) -> char {
+    SYMBOLS.get(value).copied().unwrap_or('?')
+}
 
-use rand::{seq::SliceRandom, thread_rng};
+/// Gets the value of a symbol, the opposite of symbol. Letters can be either case
+///
+/// ## Returns
+///
+/// the value, or None if the character is not a symbol
+pub fn from_symbol(character: char) -> Option<usize> {
+    let character = character.to_ascii_uppercase();
+    SYMBOLS.iter().position(|symbol| *symbol == character)
+}
 
-use self::difficulties::Difficulties;
+/// The values of every position, indexed by [row][column]
+pub type Grid = Vec<Vec<Option<usize>>>;
 
-pub const BASE: usize = 3;
-pub const SIDE: usize = BASE * BASE;
+/// Gets an empty grid of a size
+fn empty_grid(size: Size) -> Grid {
+    vec![vec![None; size.side()]; size.side()]
+}
 
-/// Gets the square based on the x and y
-///
-/// ## Arguments
-///
-/// *s x is the coordinate on the x-axis,
-/// *s y is the coordinate on the y-axis
-fn get_square(x: usize, y: usize) -> usize {
-    if y < BASE {
-        if x < BASE {
-            0
-        } else if x < 6 {
-            1
-        } else {
-            2
+/// Gets the value of a position in a filled grid, where no value is seen twice in any row,
+/// column or square
+fn pattern(size: Size, r: usize, c: usize) -> usize {
+    (size.square_columns() * (r % size.square_rows()) + r / size.square_rows() + c) % size.side()
+}
+
+/// The digits already used in each unit of the board, as bitflags
+struct Used {
+    size: Size,
+    /// The units of the rules of the board
+    units: Vec<Unit>,
+    /// The units each position is in, indexed by [row][column]
+    positions: Vec<Vec<Vec<usize>>>,
+    /// The digits used in each unit
+    used: Vec<u16>,
+}
+
+impl Used {
+    /// Collects the used digits of a grid
+    ///
+    /// ## Arguments
+    ///
+    /// * grid - The grid the digits are used in
+    /// * size - The size of the grid
+    /// * constraints - The rules of the board, which give the units
+    ///
+    /// ## Returns
+    ///
+    /// None if a digit is seen twice in any unit
+    fn from_grid(grid: &Grid, size: Size, constraints: &[Rc<dyn Constraint>]) -> Option<Self> {
+        let units = constraint::units(size, constraints);
+        let mut used = Self {
+            size,
+            positions: constraint::units_of_positions(size, &units),
+            used: vec![0; units.len()],
+            units,
+        };
+        for (row, each) in grid.iter().enumerate() {
+            for (column, value) in each.iter().enumerate() {
+                if let Some(value) = value {
+                    if !used.allows(row, column, *value) {
+                        return None;
+                    }
+                    used.toggle(row, column, *value);
+                }
+            }
         }
-    } else if y < 6 {
-        if x < BASE {
-            3
-        } else if x < 6 {
-            4
-        } else {
-            5
+        Some(used)
+    }
+
+    /// Gets the digits used in any unit of a position
+    fn seen(&self, row: usize, column: usize) -> u16 {
+        self.positions[row][column]
+            .iter()
+            .fold(0, |seen, unit| seen | self.used[*unit])
+    }
+
+    /// Tests if a value can be placed at a position
+    fn allows(&self, row: usize, column: usize, value: usize) -> bool {
+        self.seen(row, column) & (1 << value) == 0
+    }
+
+    /// Marks a value as used, or unused if it already was
+    fn toggle(&mut self, row: usize, column: usize, value: usize) {
+        for unit in self.positions[row][column].iter() {
+            self.used[*unit] ^= 1 << value;
         }
-    } else {
-        if x < BASE {
-            6
-        } else if x < 6 {
-            7
-        } else {
-            8
+    }
+
+    /// Gets the values which can still be placed at a position
+    fn candidates(&self, row: usize, column: usize) -> u16 {
+        !self.seen(row, column) & self.size.all()
+    }
+
+    /// Finds the value with the fewest places left in any unit
+    ///
+    /// ## Arguments
+    ///
+    /// * grid - The grid the digits are used in
+    /// * limit - The number of places to find fewer than
+    ///
+    /// ## Returns
+    ///
+    /// the places as (row, column, value), or None if no value has fewer places than the limit
+    fn fewest_places(&self, grid: &Grid, limit: usize) -> Option<Vec<(usize, usize, usize)>> {
+        let side = self.size.side();
+        let mut limit = limit;
+        let mut fewest = None;
+
+        for (index, unit) in self.units.iter().enumerate() {
+            let mut counts = [0; u16::BITS as usize];
+            for (row, column) in unit.cells.iter() {
+                if grid[*row][*column].is_some() {
+                    continue;
+                }
+                let candidates = self.candidates(*row, *column);
+                for (value, count) in counts.iter_mut().enumerate().take(side) {
+                    *count += (candidates >> value) as usize & 1;
+                }
+            }
+            for (value, count) in counts.iter().enumerate().take(side) {
+                if self.used[index] & (1 << value) == 0 && *count < limit {
+                    limit = *count;
+                    fewest = Some((index, value));
+                }
+            }
         }
+
+        let (index, value) = fewest?;
+        let places = self.units[index]
+            .cells
+            .iter()
+            .filter(|(row, column)| {
+                grid[*row][*column].is_none() && self.candidates(*row, *column) & (1 << value) != 0
+            })
+            .map(|(row, column)| (*row, *column, value))
+            .collect();
+        Some(places)
     }
 }
 
-/// Method used to get the indexes for the two arrays
+/// Backtracking search over the empty positions of a grid
+///
+/// Always continues with the position with the fewest candidates, or with the value with even
+/// fewer places left in a unit. When the limit is reached the search stops, and
+/// the grid is left holding the last solution found
 ///
 /// ## Arguments
 ///
-/// * x - is the global x position (0 - 8)
-/// * y - is the global y position (0 - 8)
+/// * grid - The grid to fill
+/// * used - The digits used in the grid
+/// * limit - The number of solutions after which to stop
 ///
 /// ## Returns
-/// a tuple of (first, second)
-pub fn get_index(x: usize, y: usize) -> (usize, usize) {
-    let first_array = get_square(x, y);
-    let x = x % BASE;
-    let y = y % BASE;
-    let second_array = (y * BASE) + x;
-    (first_array, second_array)
-}
+///
+/// the number of solutions found, at most limit
+fn search(grid: &mut Grid, used: &mut Used, limit: usize) -> usize {
+    let mut best: Option<(usize, usize, u16)> = None;
+    for (row, each) in grid.iter().enumerate() {
+        for (column, value) in each.iter().enumerate() {
+            if value.is_some() {
+                continue;
+            }
+            let candidates = used.candidates(row, column);
+            if best.is_none_or(|(_, _, b)| candidates.count_ones() < b.count_ones()) {
+                best = Some((row, column, candidates));
+            }
+        }
+    }
 
-fn pattern(r: usize, c: usize) -> usize {
-    (BASE * (r % BASE) + r / BASE + c) % SIDE
-}
+    let (row, column, candidates) = match best {
+        Some(best) => best,
+        // No empty positions left, so the grid is a solution
+        None => return 1,
+    };
+
+    let mut choices = (0..used.size.side())
+        .filter(|value| candidates & (1 << value) != 0)
+        .map(|value| (row, column, value))
+        .collect::<Vec<(usize, usize, usize)>>();
+    if choices.len() > 1 {
+        if let Some(places) = used.fewest_places(grid, choices.len()) {
+            choices = places;
+        }
+    }
 
-/// Used to remove values from the board
-fn removal(position: &mut [[Option<usize>; SIDE]; SIDE], diff: &Difficulties) -> usize {
-    let squares = SIDE * SIDE;
-    let empties = (squares * 3) / diff.value();
-    let mut vec = (0..squares).collect::<Vec<usize>>();
-    vec.shuffle(&mut thread_rng());
+    let mut found = 0;
+    for (row, column, value) in choices {
+        grid[row][column] = Some(value);
+        used.toggle(row, column, value);
+        found += search(grid, used, limit - found);
+        if found >= limit {
+            return found;
+        }
+        used.toggle(row, column, value);
+        grid[row][column] = None;
+    }
+    found
+}
 
-    for each in vec[0..empties].iter() {
-        position[each%SIDE][each/SIDE] = None;
+/// Finds a random filled grid which follows the rules
+///
+/// Places a random order of the values along the diagonal from the top left, and searches for a
+/// solution from there. Starts over with another order when there is none
+///
+/// ## Arguments
+///
+/// * size - The size of the grid
+/// * constraints - The rules the grid has to follow
+/// * rng - The random numbers to pick the order from
+fn random_solution(size: Size, constraints: &[Rc<dyn Constraint>], rng: &mut impl Rng) -> Grid {
+    let mut values = (0..size.side()).collect::<Vec<usize>>();
+    loop {
+        values.shuffle(rng);
+        let mut grid = empty_grid(size);
+        for (position, value) in values.iter().enumerate() {
+            grid[position][position] = Some(*value);
+        }
+        if let Some(mut used) = Used::from_grid(&grid, size, constraints) {
+            if search(&mut grid, &mut used, 1) == 1 {
+                return grid;
+            }
+        }
     }
-    empties
 }
 
 /// is the matrix of which the sudoku-square is
 /// [position](../position/struct.Position.html)
+#[derive(Clone)]
 pub struct Board {
-    pub empty: Box<[[Option<usize>; SIDE]; SIDE]>,
-    pub tries: Box<[[Option<usize>; SIDE]; SIDE]>,
+    /// The shape of the board and of its squares
+    pub size: Size,
+    pub empty: Grid,
+    pub tries: Grid,
+    /// The candidates noted by the player at each position, as bitflags
+    pub notes: Vec<Vec<u16>>,
+    /// The variant the board is played by
+    pub variant: Variant,
+    /// The rules the values have to follow, given by the variant
+    pub constraints: Vec<Rc<dyn Constraint>>,
     pub empty_squares: usize,
     pub filled_squares: usize,
 }
 
-const NUMBERS: [usize; 9] = [0, 1, 2, 3, 4, 5, 6, 7, 8];
+/// What a single character of a board string holds
+enum Cell {
+    Blank,
+    /// A number entered by the player
+    Entered(usize),
+    /// A number preset in the puzzle
+    Given(usize),
+}
+
+/// How many puzzles to generate on the classic board while looking for one of the difficulty,
+/// fewer are generated on the larger boards as each takes longer
+const GENERATION_ATTEMPTS: usize = 50;
 
 impl Board {
 
-    /// Creates a new board, with all positions filled
+    /// Creates a new board, with values removed based on the difficulty
+    ///
+    /// Generates puzzles until one needs the techniques of the difficulty. If none is found in
+    /// time, the one rated closest is used. Puzzles which can not be solved without guessing are
+    /// never used, if every one of them is the values are removed as for an easy puzzle instead.
+    /// The same seed gives the same board every time
+    ///
+    /// ## Arguments
+    ///
+    /// * difficulty - The difficulty of the board
+    /// * size - The size of the board
+    /// * variant - The rules of the board
+    /// * seed - The seed of the random numbers used to create the board
     ///
     /// ## Return
     ///
-    /// a board with all positions uniquely filled
-    pub fn new(difficulty: &Difficulties) -> Self {
-        let mut positions = [[None; SIDE]; SIDE];
+    /// a board with exactly one solution
+    pub fn new_seeded(difficulty: &Difficulties, size: Size, variant: Variant, seed: u64) -> Self {
+        let classic = Size::default().cells();
+        let attempts = GENERATION_ATTEMPTS * classic * classic / (size.cells() * size.cells());
+        let mut closest: Option<((u8, usize), Self)> = None;
+        let mut rng = StdRng::seed_from_u64(seed);
+
+        for _ in 0..attempts.clamp(1, GENERATION_ATTEMPTS) {
+            let board = Self::generate(difficulty, size, variant, &mut rng);
+            let rating = Rating::new(&board);
+            let level = match rating.difficulty() {
+                Some(level) => level.to_num(),
+                None => continue,
+            };
+            let target = difficulty.to_num();
+            if level == target {
+                return board;
+            }
 
-        let mut rng = thread_rng();
+            // Inside a level, the score tells how close to the next level the puzzle is
+            let distance = if level < target {
+                (target - level, usize::MAX - rating.score)
+            } else {
+                (level - target, rating.score)
+            };
+            if closest.as_ref().is_none_or(|(closest, _)| distance < *closest) {
+                closest = Some((distance, board));
+            }
+        }
+        if let Some((_, board)) = closest {
+            return board;
+        }
+
+        loop {
+            let board = Self::generate(&Difficulties::Easy, size, variant, &mut rng);
+            if Rating::new(&board).solved {
+                return board;
+            }
+        }
+    }
+
+    /// Generates a board, with values removed based on the difficulty
+    fn generate(
+        difficulty: &Difficulties,
+        size: Size,
+        variant: Variant,
+        rng: &mut impl Rng,
+    ) -> Self {
+        let mut positions = empty_grid(size);
+        let numbers = (0..size.side()).collect::<Vec<usize>>();
 
-        let mut rows = NUMBERS;
-        rows.shuffle(&mut rng);
-        let mut cols = NUMBERS;
-        cols.shuffle(&mut rng);
+        let mut rows = numbers.clone();
+        rows.shuffle(rng);
+        let mut cols = numbers.clone();
+        cols.shuffle(rng);
 
-        let mut nums = NUMBERS;
-        nums.shuffle(&mut rng);
+        let mut nums = numbers;
+        nums.shuffle(rng);
 
         for r in rows.iter() {
             for c in cols.iter() {
-                positions[*r][*c] = Some(nums[pattern(*r, *c)]);
+                positions[*r][*c] = Some(nums[pattern(size, *r, *c)]);
             }
         }
 
-        let empty_squares = removal(&mut positions, difficulty) + 1;
+        let constraints = variant.constraints();
+        // The pattern only follows the classic rules, so other rules need a solution of their own
+        if Used::from_grid(&positions, size, &constraints).is_none() {
+            positions = random_solution(size, &constraints, rng);
+        }
 
-        Self {
-            empty: Box::new(positions),
-            tries: Box::new(positions),
-            empty_squares,
+        let mut board = Self {
+            size,
+            empty: positions.clone(),
+            tries: positions,
+            notes: vec![vec![0; size.side()]; size.side()],
+            variant,
+            constraints,
+            empty_squares: 0,
             filled_squares: 0,
+        };
+        board.remove_values(difficulty, rng);
+        board
+    }
+
+    /// Used to remove values from the starting-board, based on the difficulty
+    ///
+    /// Removes one value at a time, and only keeps the removal if the board still has exactly one
+    /// solution. Stops when enough values are removed, or when no more can be removed
+    fn remove_values(&mut self, diff: &Difficulties, rng: &mut impl Rng) {
+        let side = self.size.side();
+        let squares = self.size.cells();
+        let empties = ((squares * 3) / diff.value()).min(self.size.max_empty());
+        let mut vec = (0..squares).collect::<Vec<usize>>();
+        vec.shuffle(rng);
+
+        for each in vec.iter() {
+            if self.empty_squares == empties {
+                break;
+            }
+            let value = self.empty[each%side][each/side].take();
+            if self.has_unique_solution() {
+                self.empty_squares += 1;
+            } else {
+                self.empty[each%side][each/side] = value;
+            }
         }
+        self.tries = self.empty.clone();
     }
 
     /// Create a mew empty board, with all positions filled with no value
-    pub fn new_empty() -> Self {
-        let positions = [[None; SIDE]; SIDE];
+    pub fn new_empty(size: Size) -> Self {
         Self {
-            empty: Box::new(positions),
-            tries: Box::new(positions),
-            empty_squares: SIDE* SIDE,
+            size,
+            empty: empty_grid(size),
+            tries: empty_grid(size),
+            notes: vec![vec![0; size.side()]; size.side()],
+            variant: Variant::default(),
+            constraints: Variant::default().constraints(),
+            empty_squares: size.cells(),
             filled_squares: 0,
         }
     }
 
+    /// Changes the rules the board is played by
+    ///
+    /// ## Arguments
+    ///
+    /// * variant - The rules to play by
+    pub fn set_variant(&mut self, variant: Variant) {
+        self.variant = variant;
+        self.constraints = variant.constraints();
+    }
+
     /// Used to create and fill a board with values
     ///
     /// ## Arguments
     ///
     /// * info -is a vec of vec with usizes to fill each position in the board.
-    ///         inner vecs represent a square
-    pub fn with_squares(info: [[usize; SIDE]; SIDE]) -> Self {
-        let mut filled = [[None; SIDE]; SIDE];
+    ///   inner vecs represent a square
+    #[cfg(test)]
+    pub fn with_squares<const SIDE: usize>(info: [[usize; SIDE]; SIDE]) -> Self {
+        let size = Size::new(SIDE).expect("The board has one of the sizes");
+        let mut filled = empty_grid(size);
 
         for (first, outer_each) in info.iter().enumerate() {
             for (second, inner_each) in outer_each.iter().enumerate() {
-                let (first, second) = get_index(second, first);
-                if *inner_each < 9 {
+                let (first, second) = size.square_position(first, second);
+                if *inner_each < SIDE {
                     filled[first][second] = Some(*inner_each);
                 } else {
                     filled[first][second] = None;
@@ -153,12 +461,7 @@ impl Board {
             }
         }
 
-        Self {
-            empty: Box::new(filled),
-            tries: Box::new(filled),
-            empty_squares: SIDE* SIDE,
-            filled_squares: 0,
-        }
+        Self::with_grid(size, filled)
     }
 
     /// Used to create and fill a board with values
@@ -166,13 +469,15 @@ impl Board {
     /// ## Arguments
     ///
     /// * info -is a vec of vec with usizes to fill each position in the board.
-    ///         inner vecs represent a square
-    pub fn with_rows(info: [[usize; SIDE]; SIDE]) -> Self {
-        let mut filled = [[None; SIDE]; SIDE];
+    ///   inner vecs represent a row
+    #[cfg(test)]
+    pub fn with_rows<const SIDE: usize>(info: [[usize; SIDE]; SIDE]) -> Self {
+        let size = Size::new(SIDE).expect("The board has one of the sizes");
+        let mut filled = empty_grid(size);
 
         for (first, outer_each) in info.iter().enumerate() {
             for (second, inner_each) in outer_each.iter().enumerate() {
-                if *inner_each < 9 {
+                if *inner_each < SIDE {
                     filled[first][second] = Some(*inner_each);
                 } else {
                     filled[first][second] = None;
@@ -180,186 +485,389 @@ impl Board {
             }
         }
 
+        Self::with_grid(size, filled)
+    }
+
+    /// Used to create a board where all values of a grid are preset
+    #[cfg(test)]
+    fn with_grid(size: Size, filled: Grid) -> Self {
+        let empty_squares = filled.iter().flatten().filter(|each| each.is_none()).count();
+
         Self {
-            empty: Box::new(filled),
-            tries: Box::new(filled),
-            empty_squares: SIDE * SIDE,
+            size,
+            empty: filled.clone(),
+            tries: filled,
+            notes: vec![vec![0; size.side()]; size.side()],
+            variant: Variant::default(),
+            constraints: Variant::default().constraints(),
+            empty_squares,
             filled_squares: 0,
         }
     }
 
-    /// Used to create a board from a string where all numbers are entered, or'.' for a None
-    /// Preset values are denoted by letters, with 'a' being 0
+    /// Used to create a board from a string where all numbers are entered as hexadecimal digits,
+    /// or'.' for a None. Preset values are denoted by letters, with 'a' being 0. The size of the
+    /// board is given by the length of the string
+    ///
+    /// ## Arguments
+    ///
+    /// * string - The string to turn into a board
+    ///
+    /// ## Returns
+    ///
+    /// the board, or the first position which could not be read
+    pub fn parse(string: &str) -> Result<Self, ParseError> {
+        Self::parse_with(string, |character| match character {
+            '.' => Some(Cell::Blank),
+            '0'..='9' | 'A'..='F' => character.to_digit(16).map(|val| Cell::Entered(val as usize)),
+            'a'..='p' => Some(Cell::Given(character as usize - 'a' as usize)),
+            _ => None,
+        })
+    }
+
+    /// Used to create a board from the common one-line format, where every number is preset and
+    /// written as the digits 1-9 and then the letters A-G, with a '0' or '.' for a None. The size
+    /// of the board is given by the length of the string
+    ///
+    /// ## Arguments
+    ///
+    /// * string - The string to turn into a board
+    ///
+    /// ## Returns
+    ///
+    /// the board, or the first position which could not be read
+    pub fn parse_standard(string: &str) -> Result<Self, ParseError> {
+        Self::parse_with(string, |character| match character {
+            '0' | '.' => Some(Cell::Blank),
+            character => from_symbol(character).map(Cell::Given),
+        })
+    }
+
+    /// Used to create a board from a string with a character for each position
+    ///
+    /// A string of a length none of the sizes have is read as the classic board
     ///
     /// ## Arguments
     ///
     /// * string - The string to turn into a board
-    pub fn from_string(string: String) -> Self {
-        let mut positions = [[None; SIDE]; SIDE];
-        let mut old_positions = [[None; SIDE]; SIDE];
-        let mut empty_squares = SIDE * SIDE;
+    /// * read - Reads a character, None if it is not allowed
+    ///
+    /// ## Returns
+    ///
+    /// the board, or the first position which could not be read
+    fn parse_with(string: &str, read: impl Fn(char) -> Option<Cell>) -> Result<Self, ParseError> {
+        let size = Size::from_cells(string.chars().count()).unwrap_or_default();
+        let side = size.side();
+        let mut positions = empty_grid(size);
+        let mut old_positions = empty_grid(size);
+        let mut empty_squares = size.cells();
         let mut filled_squares = 0;
+        let mut length = 0;
 
-        for (pos, each) in string.chars().enumerate() {
-            let y = pos/SIDE;
-            let x = pos%SIDE;
-            if each == '.' {
-                continue;
-            } else {
-                let cur_val = each as usize - '0' as usize;
-                if cur_val < 9 {
-                    positions[y][x] = Some(cur_val);
+        for (position, character) in string.chars().enumerate() {
+            if position >= size.cells() {
+                return Err(ParseError::TooLong { position, character });
+            }
+            let y = position/side;
+            let x = position%side;
+            match read(character) {
+                Some(Cell::Blank) => {}
+                Some(Cell::Entered(val) | Cell::Given(val)) if val >= side => {
+                    return Err(ParseError::InvalidCharacter { position, character });
+                }
+                Some(Cell::Entered(val)) => {
+                    positions[y][x] = Some(val);
                     filled_squares += 1;
-                } else {
-                    let val = Some(each as usize - 'a' as usize);
-                    positions[y][x] = val;
-                    old_positions[y][x] = val;
+                }
+                Some(Cell::Given(val)) => {
+                    positions[y][x] = Some(val);
+                    old_positions[y][x] = Some(val);
                     empty_squares -= 1;
                 }
-            };
+                None => return Err(ParseError::InvalidCharacter { position, character }),
+            }
+            length += 1;
+        }
+        if length < size.cells() {
+            return Err(ParseError::TooShort { position: length });
         }
 
-        Self {
-            empty: Box::new(old_positions),
-            tries: Box::new(positions),
+        Ok(Self {
+            size,
+            empty: old_positions,
+            tries: positions,
+            notes: vec![vec![0; side]; side],
+            variant: Variant::default(),
+            constraints: Variant::default().constraints(),
             empty_squares,
             filled_squares,
-        }
+        })
+    }
+
+    /// Used to convert the puzzle to the common one-line format, which the numbers entered are
+    /// not a part of
+    ///
+    /// ## Returns
+    ///
+    /// a string of the preset numbers as the digits 1-9 and then the letters A-G, with a '.' for
+    /// a None
+    pub fn to_standard(&self) -> String {
+        self.empty
+            .iter()
+            .flatten()
+            .map(|value| value.map_or('.', symbol))
+            .collect()
     }
 
     /// Adds a number to a position not previously filled in the starting-board
     ///
+    /// The number is removed from the notes of every position in the same row, column or square
+    ///
     /// ## Arguments
     /// * x - The position in x to fill
     /// * y - the position in y to fill
-    /// * num - the number to fill with, if < 9 then filled with that number, else None
+    /// * num - the number to fill with, from 1 to the side of the board, or 0 for None
     ///
     /// ## Returns
     /// a boolean if it worked or not
     pub fn add_number(&mut self, x: usize, y: usize, num: usize) -> bool {
+        if num > self.size.side() {
+            return false;
+        }
         let num = if num > 0 {
             Some(num - 1)
         } else {
             None
         };
-        if self.empty[y][x].is_none() {
-            if self[(y, x)].is_none() {
-                self.filled_squares += 1;
-            }
-            if num.is_none() {
-                self.filled_squares -= 1;
-            }
-            self[(y, x)] = num;
-            self.test_filled()
-        } else {
-            false
+        if !self.set_number(x, y, num) {
+            return false;
         }
+        if let Some(value) = num {
+            self.remove_note_from_peers(x, y, value);
+        }
+        true
     }
 
-    /// Used to test if the board has been filled
+    /// Sets a position not previously filled in the starting-board, without changing any notes
+    ///
+    /// ## Arguments
+    /// * x - The position in x to fill
+    /// * y - the position in y to fill
+    /// * num - the value to fill with
     ///
     /// ## Returns
+    /// a boolean if it worked or not
+    pub fn set_number(&mut self, x: usize, y: usize, num: Option<usize>) -> bool {
+        if self.empty[y][x].is_some() {
+            return false;
+        }
+        match (self[(y, x)], num) {
+            (None, Some(_)) => self.filled_squares += 1,
+            (Some(_), None) => self.filled_squares -= 1,
+            _ => {}
+        }
+        self[(y, x)] = num;
+        true
+    }
+
+    /// Solves the board from the preset values in the starting-board
     ///
-    /// True if the number of filled squares is the same as the number of empty squares
-    pub fn test_filled(&self) -> bool {
-        (self.filled_squares + self.empty_squares) == SIDE * SIDE
+    /// ## Returns
+    ///
+    /// a board with all positions in the tries filled, or None if the board has no solution
+    pub fn solve(&self) -> Option<Board> {
+        let mut grid = self.empty.clone();
+        let mut used = Used::from_grid(&grid, self.size, &self.constraints)?;
+        if search(&mut grid, &mut used, 1) == 0 {
+            return None;
+        }
+
+        let mut solved = self.clone();
+        solved.tries = grid;
+        solved.filled_squares = solved.empty_squares;
+        Some(solved)
     }
 
-    /// Method to test the whole board
+    /// Counts the solutions of the preset values in the starting-board
+    ///
+    /// ## Arguments
+    ///
+    /// * limit - The number of solutions after which to stop counting
     ///
     /// ## Returns
-    /// true if the board is correct, else false
-    pub fn test_board(&self) -> bool {
-        let mut returnval = true;
-        for each in 0..SIDE {
-            returnval &= self.test_row(each);
-            returnval &= self.test_column(each);
-            returnval &= self.test_square(each);
+    ///
+    /// the number of solutions, at most limit
+    pub fn count_solutions(&self, limit: usize) -> usize {
+        let mut grid = self.empty.clone();
+        match Used::from_grid(&grid, self.size, &self.constraints) {
+            Some(mut used) if limit > 0 => search(&mut grid, &mut used, limit),
+            _ => 0,
         }
-        returnval
     }
 
-    /// Method to test a given row for if it is correct
+    /// Tests if the preset values in the starting-board has exactly one solution
+    pub fn has_unique_solution(&self) -> bool {
+        self.count_solutions(2) == 1
+    }
+
+    /// Toggles a note at a position not previously filled in the starting-board
     ///
     /// ## Arguments
+    /// * x - The position in x to note
+    /// * y - the position in y to note
+    /// * num - the number to note, from 1 to the side of the board
     ///
-    /// * row - is the row to test
     /// ## Returns
-    ///  true if no number is seen twice, and all < 9
-    pub fn test_row(&self, row: usize) -> bool {
-        let mut tests = 0b000000000;
-        for column in 0..SIDE {
-            let pos = self[(row, column)];
-            if let Some(value) = pos {
-                let pos = 1 << value;
-                if ((tests & pos) >> value) != 1 {
-                    tests |= pos;
-                } else {
-                    return false;
+    /// a boolean if it worked or not
+    pub fn toggle_note(&mut self, x: usize, y: usize, num: usize) -> bool {
+        if num == 0 || num > self.size.side() || self.empty[y][x].is_some() {
+            return false;
+        }
+        self.notes[y][x] ^= 1 << (num - 1);
+        true
+    }
+
+    /// Removes all notes at a position
+    ///
+    /// ## Arguments
+    /// * x - The position in x to clear
+    /// * y - the position in y to clear
+    pub fn clear_notes(&mut self, x: usize, y: usize) {
+        self.notes[y][x] = 0;
+    }
+
+    /// Fills the notes of every empty position with the numbers the rules allow
+    pub fn fill_notes(&mut self) {
+        for row in 0..self.size.side() {
+            for column in 0..self.size.side() {
+                if self[(row, column)].is_none() {
+                    self.notes[row][column] = self.candidates(row, column);
                 }
-            } else {
-                return false;
             }
         }
-        0b111111111 == tests
     }
 
-    /// Method to test a given column for if it is correct
+    /// Gets the units of every rule of the board
+    ///
+    /// The rows come first, then the columns and the squares, and last the units of any other
+    /// rules
+    pub fn units(&self) -> Vec<Unit> {
+        constraint::units(self.size, &self.constraints)
+    }
+
+    /// Tests if every rule allows a value at a position, given the other values in the tries
     ///
     /// ## Arguments
+    /// * row - The row of the position
+    /// * column - The column of the position
+    /// * value - The value to test
+    pub fn allows(&self, row: usize, column: usize, value: usize) -> bool {
+        self.constraints
+            .iter()
+            .all(|constraint| constraint.allows(&self.tries, self.size, (row, column), value))
+    }
+
+    /// Gets the numbers every rule allows at a position, given the other values in the tries
     ///
-    /// * column - The column to test
+    /// ## Returns
     ///
-    /// ## Return
+    /// the numbers as bitflags
+    pub fn candidates(&self, row: usize, column: usize) -> u16 {
+        self.constraints.iter().fold(self.size.all(), |candidates, constraint| {
+            candidates & constraint.candidates(&self.tries, self.size, (row, column))
+        })
+    }
+
+    /// Removes a number from the notes of every position sharing a unit with a position
+    fn remove_note_from_peers(&mut self, x: usize, y: usize, value: usize) {
+        for unit in self.units() {
+            if unit.cells.contains(&(y, x)) {
+                for (row, column) in unit.cells.into_iter().filter(|other| *other != (y, x)) {
+                    self.notes[row][column] &= !(1 << value);
+                }
+            }
+        }
+    }
+
+    /// Gets every position holding a number the rules do not allow, as it is also held by
+    /// another position in one of its units
     ///
-    /// true if no number is seen twice, and all < 9
-    pub fn test_column(&self, column: usize) -> bool {
-        let mut tests = 0b000000000;
-        for row in 0..SIDE {
-            let pos = self[(row, column)];
-            if let Some(value) = pos {
-                let pos = 1 << value;
-                if ((tests & pos) >> value) != 1 {
-                    tests |= pos;
-                } else {
-                    return false;
+    /// ## Returns
+    ///
+    /// the positions as (y, x), in order
+    pub fn conflicts(&self) -> Vec<(usize, usize)> {
+        let side = self.size.side();
+        (0..side)
+            .flat_map(|row| (0..side).map(move |column| (row, column)))
+            .filter(|(row, column)| {
+                self[(*row, *column)].is_some_and(|value| !self.allows(*row, *column, value))
+            })
+            .collect()
+    }
+
+    /// Used to read the notes from the line format of older saves, the notes of every position
+    /// as bitflags, row by row, separated by ','
+    ///
+    /// ## Returns
+    ///
+    /// a boolean if it worked or not, the notes are unchanged if not
+    pub fn read_notes(&mut self, string: &str) -> bool {
+        let notes = string
+            .split(',')
+            .map(|notes| notes.parse::<u16>().ok())
+            .map(|notes| notes.filter(|notes| notes & !self.size.all() == 0))
+            .collect::<Option<Vec<u16>>>();
+        match notes {
+            Some(notes) if notes.len() == self.size.cells() => {
+                let side = self.size.side();
+                for (pos, notes) in notes.into_iter().enumerate() {
+                    self.notes[pos / side][pos % side] = notes;
                 }
-            } else {
-                return false;
+                true
             }
+            _ => false,
         }
-        0b111111111 == tests
     }
 
-    /// Method to test a square for if it is correct
+    /// Used to test if the board has been filled
+    ///
+    /// ## Returns
+    ///
+    /// True if the number of filled squares is the same as the number of empty squares
+    pub fn test_filled(&self) -> bool {
+        self.filled_squares == self.empty_squares
+    }
+
+    /// Method to test the whole board
+    ///
+    /// ## Returns
+    /// true if every position is filled with a number the rules allow, else false
+    pub fn test_board(&self) -> bool {
+        self.units().iter().all(|unit| self.test_unit(&unit.cells))
+    }
+
+    /// Method to test a unit of positions for if it is correct
     ///
     /// ## Arguments
     ///
-    /// * square - is  the square to test
-    /// [
-    ///     0, 1, 2,
-    ///     3, 4, 5,
-    ///     6, 7, 8
-    /// ]
+    /// * unit - The positions to test, as (row, column)
+    ///
     /// ## Returns
-    /// true if no number is seen twice, and all < 9
-    pub fn test_square(&self, square: usize) -> bool {
-        let mut tests = 0b000000000;
-        for position in 0..SIDE {
-            let (first, second) = get_index(square, position);
-            let pos = self[(first, second)];
-            if let Some(value) = pos {
+    /// true if every position holds a number and no number is seen twice
+    fn test_unit(&self, unit: &[(usize, usize)]) -> bool {
+        let mut tests = 0;
+        for position in unit {
+            if let Some(value) = self[*position] {
                 let pos = 1 << value;
-                if ((tests & pos) >> value) != 1 {
-                    tests |= pos;
-                } else {
+                if tests & pos != 0 {
                     return false;
                 }
+                tests |= pos;
             } else {
                 return false;
             }
         }
-        0b111111111 == tests
+        true
     }
 }
 
@@ -369,21 +877,22 @@ impl std::fmt::Display for Board {
     ///
     /// ## Returns
     /// 
-    /// a string where the value None is a '.', a value in the tries is the number,
-    /// and a value in the empty as a char, with 'a' == 0
+    /// a string where the value None is a '.', a value in the tries is the number as a
+    /// hexadecimal digit, and a value in the empty as a char, with 'a' == 0
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let mut output = vec![];
         for (y, each) in self.tries.iter().enumerate() {
             for (x, value) in each.iter().enumerate() {
                 output.push(if let Some(value) = value {
-                    if self.empty[y][x] == None {
-                        *value as u8 + b'0'
+                    if self.empty[y][x].is_none() {
+                        std::char::from_digit(*value as u32, 16)
+                            .map_or('?', |digit| digit.to_ascii_uppercase())
                     } else {
-                        *value as u8 + b'a'
+                        (*value as u8 + b'a') as char
                     }
                 } else {
-                    b'.'
-                } as char);
+                    '.'
+                });
             }
         }
         let output = String::from_iter(output);
@@ -510,20 +1019,65 @@ mod board_test {
     }
 
     #[test]
-    fn test_from_string() {
+    fn test_parse() {
         let test_string = "0b2345678345678012678012345120453786453786120786120453201534867534867201867201534";
-        let test_board = Board::from_string(test_string.to_string());
+        let test_board = Board::parse(test_string).expect("The board is valid");
 
         assert!(test_board.test_board());
         assert!(test_board[(1, 1)] == Some(4));
 
         let test_string = "abcdefghidefghiabcghiabcdefbcaefdhigefdhigbcahigbcaefdcabfdeighfdeighcabighcabfde";
-        let test_board = Board::from_string(test_string.to_string());
+        let test_board = Board::parse(test_string).expect("The board is valid");
 
         assert!(test_board.test_board());
         assert!(test_board[(1, 1)] == Some(4));
     }
 
+    #[test]
+    fn test_parse_error() {
+        let test_string = "abcdefghidefghiabcghiabcdefbcaefdhigefdhigbcahigbcaefdcabfdeighfdeighcabighcabfde";
+
+        assert_eq!(
+            Board::parse(&test_string[..80]).err(),
+            Some(ParseError::TooShort { position: 80 })
+        );
+        assert_eq!(
+            Board::parse(&format!("{}e", test_string)).err(),
+            Some(ParseError::TooLong { position: 81, character: 'e' })
+        );
+        assert_eq!(
+            Board::parse(&test_string.replacen('e', "/", 1)).err(),
+            Some(ParseError::InvalidCharacter { position: 4, character: '/' })
+        );
+        assert_eq!(
+            Board::parse(&test_string.replacen('e', "9", 1)).err(),
+            Some(ParseError::InvalidCharacter { position: 4, character: '9' })
+        );
+        assert_eq!(
+            Board::parse(&test_string.replacen('e', "j", 1)).err(),
+            Some(ParseError::InvalidCharacter { position: 4, character: 'j' })
+        );
+    }
+
+    #[test]
+    fn test_parse_standard() {
+        let test_string = "53..7....6..195....98....6.8...6...34..8.3..17...2...6.6....28....419..5....8..79";
+        let test_board = Board::parse_standard(test_string).expect("The board is valid");
+
+        assert_eq!(test_board.empty[0][0], Some(4));
+        assert_eq!(test_board.empty[0][2], None);
+        assert_eq!(test_board.empty_squares, 51);
+        assert_eq!(test_board.to_standard(), test_string);
+        let test_board =
+            Board::parse_standard(&test_string.replace('.', "0")).expect("The board is valid");
+
+        assert_eq!(test_board.to_standard(), test_string);
+        assert_eq!(
+            Board::parse_standard(&test_string.replacen('5', "a", 1)).err(),
+            Some(ParseError::InvalidCharacter { position: 0, character: 'a' })
+        );
+    }
+
     #[test]
     fn test_to_string() {
         let test_string = "abcdefghidefghiabcghiabcdefbcaefdhigefdhigbcahigbcaefdcabfdeighfdeighcabighcabfde";
@@ -532,6 +1086,184 @@ mod board_test {
         assert_eq!(test_string, test_board.to_string());
     }
 
+    #[test]
+    fn test_solve() {
+        let test_string = "ec..g....f..aie....ih....f.h...f...cd..h.c..ag...b...f.f....bh....dai..e....h..gi";
+        let solution = "ecdfghiabfgbaiecdhaihcdbefgheigfadbcdbfhecgiagacibdhefifaecgbhdbhgdaifcecdebhfagi";
+        let board = Board::parse(test_string).expect("The board is valid");
+
+        let solved = board.solve().expect("The board has a solution");
+
+        assert!(solved.test_board());
+        assert!(solved.test_filled());
+        assert_eq!(solved.tries, Board::parse(solution).expect("The board is valid").tries);
+        assert_eq!(solved.empty, board.empty);
+    }
+
+    #[test]
+    fn test_solve_no_solution() {
+        let board = get_board_with_false_values();
+
+        assert!(board.solve().is_none());
+
+        // Two 0s in the first row, with the rest of the board empty
+        let test_string = format!("{:.<81}", "a.......a");
+        let board = Board::parse(&test_string).expect("The board is valid");
+
+        assert!(board.solve().is_none());
+    }
+
+    #[test]
+    fn test_count_solutions() {
+        let test_string = "ec..g....f..aie....ih....f.h...f...cd..h.c..ag...b...f.f....bh....dai..e....h..gi";
+        let board = Board::parse(test_string).expect("The board is valid");
+
+        assert_eq!(board.count_solutions(10), 1);
+        assert!(board.has_unique_solution());
+
+        // Only the first row is given, so there are many solutions
+        let test_string = format!("{:.<81}", "abcdefghi");
+        let board = Board::parse(&test_string).expect("The board is valid");
+
+        assert_eq!(board.count_solutions(5), 5);
+        assert_eq!(board.count_solutions(0), 0);
+        assert!(!board.has_unique_solution());
+
+        let board = get_board_with_false_values();
+
+        assert_eq!(board.count_solutions(2), 0);
+    }
+
+    #[test]
+    fn test_new_is_unique() {
+        for difficulty in [Difficulties::Easy, Difficulties::Medium, Difficulties::Hard] {
+            let seed = rand::random();
+            let board = Board::new_seeded(&difficulty, Size::default(), Variant::default(), seed);
+
+            assert!(board.has_unique_solution());
+            let empties = board.empty.iter().flatten().filter(|each| each.is_none()).count();
+            assert_eq!(board.empty_squares, empties);
+        }
+    }
+
+    #[test]
+    fn test_new_is_rated() {
+        for difficulty in [Difficulties::Easy, Difficulties::Medium, Difficulties::Hard] {
+            let board = Board::new_seeded(&difficulty, Size::default(), Variant::default(), 42);
+
+            assert_eq!(Rating::new(&board).difficulty(), Some(difficulty));
+        }
+    }
+
+    #[test]
+    fn test_new_seeded() {
+        let (size, variant) = (Size::default(), Variant::default());
+        let board = Board::new_seeded(&Difficulties::Medium, size, variant, 42);
+
+        assert_eq!(board.to_string(), Board::new_seeded(&Difficulties::Medium, size, variant, 42).to_string());
+        assert_ne!(board.to_string(), Board::new_seeded(&Difficulties::Medium, size, variant, 43).to_string());
+    }
+
+    #[test]
+    fn test_new_variant() {
+        for side in [4, 9] {
+            let size = Size::new(side).expect("The side is one of the sizes");
+            let board = Board::new_seeded(&Difficulties::Easy, size, Variant::X, 42);
+            let solved = board.solve().expect("The board has a solution");
+
+            assert_eq!(board.variant, Variant::X);
+            assert!(board.has_unique_solution());
+            assert!(solved.test_board());
+            // Both diagonals hold every number
+            let diagonals = constraint::Diagonals.units(size);
+            assert!(diagonals.iter().all(|diagonal| solved.test_unit(&diagonal.cells)));
+        }
+    }
+
+    #[test]
+    fn test_new_variant_is_solved() {
+        for side in [9, 12] {
+            let size = Size::new(side).expect("The side is one of the sizes");
+            let board = Board::new_seeded(&Difficulties::Hard, size, Variant::X, 42);
+
+            assert_eq!(board.variant, Variant::X);
+            assert!(Rating::new(&board).solved);
+            assert!(board.empty_squares <= size.max_empty());
+        }
+    }
+
+    #[test]
+    fn test_new_sizes() {
+        for side in [4, 6, 12] {
+            let size = Size::new(side).expect("The side is one of the sizes");
+            let board = Board::new_seeded(&Difficulties::Easy, size, Variant::default(), 42);
+
+            assert_eq!(board.size, size);
+            assert_eq!(board.empty.len(), side);
+            assert!(board.has_unique_solution());
+            assert!(board.solve().expect("The board has a solution").test_board());
+        }
+    }
+
+    #[test]
+    fn test_new_sizes_are_solved() {
+        let size = Size::new(16).expect("16 is a size");
+        let board = Board::new_seeded(&Difficulties::Hard, size, Variant::default(), 42);
+
+        assert!(Rating::new(&board).solved);
+        assert!(board.empty_squares <= size.max_empty());
+    }
+
+    #[test]
+    fn test_parse_sizes() {
+        // Two squares across, each two rows tall and three columns wide
+        let test_string = "abcdefdefabcbcaefdefdbcacabfdefdecab";
+        let test_board = Board::parse(test_string).expect("The board is valid");
+
+        assert_eq!(test_board.size, Size::new(6).expect("6 is a size"));
+        assert!(test_board.test_board());
+        assert_eq!(test_board.to_string(), test_string);
+
+        let test_board = Board::parse_standard("1..4.4...3..2..1").expect("The board is valid");
+
+        assert_eq!(test_board.size, Size::new(4).expect("4 is a size"));
+        assert_eq!(test_board.empty[3][3], Some(0));
+        assert_eq!(
+            Board::parse_standard("1..5.4...3..2..1").err(),
+            Some(ParseError::InvalidCharacter { position: 3, character: '5' })
+        );
+    }
+
+    #[test]
+    fn test_symbols() {
+        assert_eq!(symbol(0), '1');
+        assert_eq!(symbol(9), 'A');
+        assert_eq!(symbol(15), 'G');
+        assert_eq!(from_symbol('9'), Some(8));
+        assert_eq!(from_symbol('g'), Some(15));
+        assert_eq!(from_symbol('0'), None);
+        assert_eq!(from_symbol('H'), None);
+    }
+
+    #[test]
+    fn test_large_values() {
+        let mut test_board = Board::new_empty(Size::new(16).expect("16 is a size"));
+        test_board.empty[0][0] = Some(15);
+        test_board.tries[0][0] = Some(15);
+        assert!(test_board.add_number(1, 0, 11));
+        assert!(test_board.add_number(0, 1, 16));
+
+        // The entries are written in hex, and the givens as the letters after 'a'
+        let string = test_board.to_string();
+        assert!(string.starts_with("pA.............."));
+        assert_eq!(Board::parse(&string).map(|board| board.tries), Ok(test_board.tries.clone()));
+
+        let standard = test_board.to_standard();
+        assert!(standard.starts_with("G..............."));
+        let from_standard = Board::parse_standard(&standard).expect("The board is valid");
+        assert_eq!(from_standard.empty[0][0], Some(15));
+    }
+
     #[test]
     fn test_add_number() {
         let mut board = get_empty_board();
@@ -541,9 +1273,184 @@ mod board_test {
         assert!(board.add_number(4, 4, 1));
     }
 
+    #[test]
+    fn test_filled() {
+        let mut board = get_empty_board();
+        let blanks = board.empty.iter().flatten().filter(|each| each.is_none()).count();
+
+        // The empty squares are the blanks of the puzzle, not every position of the board
+        assert_eq!(board.empty_squares, blanks);
+        // Entering a number works without filling the board
+        assert!(board.add_number(5, 0, 1));
+        assert!(!board.test_filled());
+
+        let solved = board.solve().expect("The board has a solution");
+
+        assert!(solved.test_filled());
+        for square in constraint::Squares.units(solved.size) {
+            assert!(solved.test_unit(&square.cells));
+        }
+
+        let board = Board::new_seeded(&Difficulties::Easy, Size::default(), Variant::default(), 42);
+        let blanks = board.empty.iter().flatten().filter(|each| each.is_none()).count();
+
+        assert_eq!(board.empty_squares, blanks);
+    }
+
+    #[test]
+    fn test_toggle_note() {
+        let mut board = get_empty_board();
+
+        assert!(!board.toggle_note(0, 0, 1));
+        assert!(!board.toggle_note(5, 0, 0));
+        assert!(board.toggle_note(5, 0, 1));
+        assert!(board.toggle_note(5, 0, 9));
+        assert_eq!(board.notes[0][5], 0b100000001);
+
+        assert!(board.toggle_note(5, 0, 1));
+        assert_eq!(board.notes[0][5], 0b100000000);
+
+        board.clear_notes(5, 0);
+        assert_eq!(board.notes[0][5], 0);
+    }
+
+    #[test]
+    fn test_fill_notes() {
+        let mut board = get_empty_board();
+
+        board.fill_notes();
+
+        // (0, 4) sees 4, 6, 7, 8 and 9, leaving 1, 2, 3 and 5
+        assert_eq!(board.notes[0][4], 0b000010111);
+        assert_eq!(board.notes[0][0], 0);
+
+        assert!(board.add_number(4, 0, 2));
+        assert_eq!(board.notes[0][5] & 0b10, 0);
+        assert_eq!(board.notes[8][4] & 0b10, 0);
+        assert_eq!(board.notes[1][3] & 0b10, 0);
+        assert_ne!(board.notes[1][0] & 0b10, 0);
+    }
+
+    #[test]
+    fn test_conflicts() {
+        let mut board = get_empty_board();
+
+        assert!(board.conflicts().is_empty());
+
+        // 9 is already in the first row, and 8 in both the first square and column
+        assert!(board.add_number(4, 0, 9));
+        assert!(board.add_number(1, 2, 8));
+
+        assert_eq!(
+            board.conflicts(),
+            vec![(0, 0), (0, 4), (0, 6), (2, 1), (8, 1)]
+        );
+
+        assert!(board.add_number(4, 0, 0));
+        assert_eq!(board.conflicts(), vec![(0, 0), (2, 1), (8, 1)]);
+    }
+
+    #[test]
+    fn test_allows() {
+        let mut board = Board::new_empty(Size::new(4).expect("4 is a size"));
+        board[(0, 0)] = Some(0);
+        board[(3, 1)] = Some(1);
+
+        assert!(!board.allows(0, 3, 0));
+        assert!(!board.allows(1, 1, 0));
+        assert!(board.allows(3, 3, 0));
+        // A position does not clash with its own value
+        assert!(board.allows(0, 0, 0));
+        assert_eq!(board.candidates(0, 1), 0b1100);
+
+        board.set_variant(Variant::X);
+        // (1, 2) is on the diagonal from the top right to the bottom left
+        board[(1, 2)] = Some(2);
+
+        assert!(!board.allows(3, 3, 0));
+        assert!(!board.allows(3, 0, 2));
+        assert_eq!(board.candidates(0, 3), 0b1010);
+    }
+
+    /// No value may be seen twice in the corners of the board
+    struct Corners;
+
+    impl Constraint for Corners {
+        fn units(&self, size: Size) -> Vec<Unit> {
+            let last = size.side() - 1;
+            vec![Unit {
+                kind: constraint::Kind::Other,
+                cells: vec![(0, 0), (0, last), (last, 0), (last, last)],
+            }]
+        }
+    }
+
+    /// The top left position holds a 1, a rule without units
+    struct OneFirst;
+
+    impl Constraint for OneFirst {
+        fn units(&self, _size: Size) -> Vec<Unit> {
+            vec![]
+        }
+
+        fn allows(&self, _: &Grid, _: Size, position: (usize, usize), value: usize) -> bool {
+            position != (0, 0) || value == 0
+        }
+    }
+
+    #[test]
+    fn test_constraints() {
+        let mut board = get_board_with_values();
+
+        assert!(board.test_board());
+        assert_eq!(board.units().len(), 27);
+
+        // The top right and bottom left corners both hold 9
+        board.constraints.push(Rc::new(Corners));
+
+        assert_eq!(board.units().len(), 28);
+        assert!(!board.test_board());
+        assert_eq!(board.conflicts(), vec![(0, 8), (8, 0)]);
+
+        let mut board = Board::new_empty(Size::new(4).expect("4 is a size"));
+        board.constraints.push(Rc::new(Corners));
+        board.empty[0][0] = Some(0);
+        board.tries[0][0] = Some(0);
+        let solved = board.solve().expect("The board has a solution");
+        let corners = [solved[(0, 3)], solved[(3, 0)], solved[(3, 3)]];
+
+        assert!(solved.test_board());
+        assert!(!corners.contains(&Some(0)));
+        assert!(!board.allows(3, 3, 0));
+        assert_eq!(board.candidates(0, 3), 0b1110);
+
+        let mut board = Board::new_empty(Size::new(4).expect("4 is a size"));
+        board.constraints.push(Rc::new(OneFirst));
+
+        assert!(!board.allows(0, 0, 1));
+        assert!(board.allows(0, 1, 1));
+        assert_eq!(board.candidates(0, 0), 0b0001);
+        assert_eq!(board.candidates(0, 1), 0b1111);
+    }
+
+    #[test]
+    fn test_read_notes() {
+        let mut board = Board::new_empty(Size::new(4).expect("4 is a size"));
+        let string = "1,2,3,4,5,6,7,8,9,10,11,12,13,14,15,0";
+
+        assert!(board.read_notes(string));
+        assert_eq!(board.notes[0], vec![1, 2, 3, 4]);
+        assert_eq!(board.notes[3], vec![13, 14, 15, 0]);
+        assert!(!board.read_notes("1,2,3"));
+        assert!(!board.read_notes(&string.replace(',', ";")));
+        // 16 is the note of a fifth number, which the board does not have
+        assert!(!board.read_notes(&string.replace("15", "16")));
+        assert_eq!(board.notes[3][2], 15);
+    }
+
     #[test]
     fn test_creation() {
-        let board = Board::new_empty();
+        let board = Board::new_empty(Size::default());
 
         assert_eq!(board[(2, 2)], None);
     }
@@ -601,46 +1508,18 @@ mod board_test {
     }
 
     #[test]
-    fn test_row() {
-        let board = get_board_with_values();
-        
-        for each in 0..SIDE {
-            assert!(board.test_row(each));
-        }
-
-        let board = get_board_with_values();
-        
-        for each in 0..SIDE {
-            assert!(board.test_row(each));
-        }
-
-        let board = get_board_with_false_values();
-        
-        for each in 0..SIDE {
-            // assert NOT
-            assert!(!board.test_row(each));
-        }
-    }
-
-    #[test]
-    fn test_column() {
-        let board = get_board_with_values();
-
-        for each in 0..SIDE {
-            assert!(board.test_column(each));
-        }
-
+    fn test_units() {
         let board = get_board_with_values();
 
-        for each in 0..SIDE {
-            assert!(board.test_column(each));
+        for unit in board.units() {
+            assert!(board.test_unit(&unit.cells));
         }
 
         let board = get_board_with_false_values();
 
-        for each in 0..SIDE {
+        for unit in board.units() {
             // assert NOT
-            assert!(!board.test_column(each));
+            assert!(!board.test_unit(&unit.cells));
         }
     }
 
@@ -649,7 +1528,7 @@ mod board_test {
         let square = 4;
         let index = 6;
 
-        let (first, second) = get_index(3, 5);
+        let (first, second) = Size::default().get_index(3, 5);
 
         assert_eq!(first, square);
         assert_eq!(second, index);
@@ -657,7 +1536,7 @@ mod board_test {
         let square = 8;
         let index = 6;
 
-        let (first, second) = get_index(6, 8);
+        let (first, second) = Size::default().get_index(6, 8);
 
         assert_eq!(first, square);
         assert_eq!(second, index);
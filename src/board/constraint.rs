@@ -0,0 +1,232 @@
+use std::rc::Rc;
+
+use super::{size::Size, Grid};
+
+/// What a unit is, so the techniques which look at how rows, columns and squares cross can
+/// find them among the units of any rule
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    Row,
+    Column,
+    Square,
+    /// A unit of any other rule, such as a diagonal
+    Other,
+}
+
+/// A group of positions where no value may be seen twice
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Unit {
+    /// What the unit is
+    pub kind: Kind,
+    /// The positions of the unit, as (row, column)
+    pub cells: Vec<(usize, usize)>,
+}
+
+/// A rule of which values the positions of a board can hold
+///
+/// A rule is made of units, groups of positions where no value may be seen twice, which the
+/// solvers work with. The board asks every rule if a placement is allowed and what the candidates
+/// of a position are, which by default comes from the units, so a rule such as a cage or a
+/// thermometer can check more than its units without changing the board
+pub trait Constraint {
+    /// Gets the groups of positions where no value may be seen twice
+    ///
+    /// ## Arguments
+    ///
+    /// * size - The size of the board
+    fn units(&self, size: Size) -> Vec<Unit>;
+
+    /// Tests if a value can be placed at a position, given the other values of a grid
+    ///
+    /// ## Arguments
+    ///
+    /// * grid - The values of the board, indexed by [row][column]
+    /// * size - The size of the board
+    /// * position - The (row, column) to place the value at
+    /// * value - The value to place
+    fn allows(&self, grid: &Grid, size: Size, position: (usize, usize), value: usize) -> bool {
+        self.units(size)
+            .iter()
+            .filter(|unit| unit.cells.contains(&position))
+            .flat_map(|unit| unit.cells.iter())
+            .all(|(row, column)| (*row, *column) == position || grid[*row][*column] != Some(value))
+    }
+
+    /// Gets the values which can be placed at a position, given the other values of a grid
+    ///
+    /// ## Arguments
+    ///
+    /// * grid - The values of the board, indexed by [row][column]
+    /// * size - The size of the board
+    /// * position - The (row, column) to get the values of
+    ///
+    /// ## Returns
+    ///
+    /// the values as bitflags
+    fn candidates(&self, grid: &Grid, size: Size, position: (usize, usize)) -> u16 {
+        (0..size.side())
+            .filter(|value| self.allows(grid, size, position, *value))
+            .fold(0, |candidates, value| candidates | 1 << value)
+    }
+}
+
+/// No value may be seen twice in a row
+pub struct Rows;
+
+impl Constraint for Rows {
+    fn units(&self, size: Size) -> Vec<Unit> {
+        let side = size.side();
+        (0..side)
+            .map(|row| Unit {
+                kind: Kind::Row,
+                cells: (0..side).map(|column| (row, column)).collect(),
+            })
+            .collect()
+    }
+}
+
+/// No value may be seen twice in a column
+pub struct Columns;
+
+impl Constraint for Columns {
+    fn units(&self, size: Size) -> Vec<Unit> {
+        let side = size.side();
+        (0..side)
+            .map(|column| Unit {
+                kind: Kind::Column,
+                cells: (0..side).map(|row| (row, column)).collect(),
+            })
+            .collect()
+    }
+}
+
+/// No value may be seen twice in a square
+pub struct Squares;
+
+impl Constraint for Squares {
+    fn units(&self, size: Size) -> Vec<Unit> {
+        let side = size.side();
+        (0..side)
+            .map(|square| Unit {
+                kind: Kind::Square,
+                cells: (0..side)
+                    .map(|position| size.square_position(square, position))
+                    .collect(),
+            })
+            .collect()
+    }
+}
+
+/// No value may be seen twice in either of the two diagonals between the corners
+pub struct Diagonals;
+
+impl Constraint for Diagonals {
+    fn units(&self, size: Size) -> Vec<Unit> {
+        let side = size.side();
+        vec![
+            (0..side).map(|row| (row, row)).collect(),
+            (0..side).map(|row| (row, side - 1 - row)).collect(),
+        ]
+        .into_iter()
+        .map(|cells| Unit {
+            kind: Kind::Other,
+            cells,
+        })
+        .collect()
+    }
+}
+
+/// Gets the rules of the classic game, the rows, then the columns and last the squares
+pub fn classic() -> Vec<Rc<dyn Constraint>> {
+    vec![Rc::new(Rows), Rc::new(Columns), Rc::new(Squares)]
+}
+
+/// Gets the units of every rule, in the order of the rules
+///
+/// ## Arguments
+///
+/// * size - The size of the board
+/// * constraints - The rules of the board
+pub fn units(size: Size, constraints: &[Rc<dyn Constraint>]) -> Vec<Unit> {
+    constraints
+        .iter()
+        .flat_map(|constraint| constraint.units(size))
+        .collect()
+}
+
+/// Gets the units each position is in, as indexes into a list of units
+///
+/// ## Arguments
+///
+/// * size - The size of the board
+/// * units - The units of the board
+///
+/// ## Returns
+///
+/// the indexes of the units, indexed by [row][column]
+pub fn units_of_positions(size: Size, units: &[Unit]) -> Vec<Vec<Vec<usize>>> {
+    let mut positions = vec![vec![vec![]; size.side()]; size.side()];
+    for (index, unit) in units.iter().enumerate() {
+        for (row, column) in unit.cells.iter() {
+            positions[*row][*column].push(index);
+        }
+    }
+    positions
+}
+
+#[cfg(test)]
+mod constraint_test {
+    use super::*;
+
+    #[test]
+    fn test_units() {
+        let size = Size::new(6).expect("6 is a size");
+        let units = units(size, &classic());
+
+        assert_eq!(units.len(), 3 * 6);
+        assert_eq!(units[0].cells[5], (0, 5));
+        assert_eq!(units[6].cells[5], (5, 0));
+        assert_eq!(units[2 * 6 + 1].cells[3], (1, 3));
+        assert_eq!(units[2 * 6 + 1].kind, Kind::Square);
+
+        let positions = units_of_positions(size, &units);
+
+        assert_eq!(positions[3][4], vec![3, 6 + 4, 2 * 6 + 3]);
+    }
+
+    #[test]
+    fn test_allows() {
+        let size = Size::new(4).expect("4 is a size");
+        let mut grid = vec![vec![None; 4]; 4];
+        grid[0][0] = Some(0);
+        grid[3][1] = Some(1);
+
+        assert!(!Rows.allows(&grid, size, (0, 3), 0));
+        assert!(Columns.allows(&grid, size, (0, 3), 0));
+        assert!(!Squares.allows(&grid, size, (1, 1), 0));
+        // A position does not clash with its own value
+        assert!(Rows.allows(&grid, size, (0, 0), 0));
+
+        assert_eq!(Columns.candidates(&grid, size, (0, 1)), 0b1101);
+        assert_eq!(Squares.candidates(&grid, size, (2, 0)), 0b1101);
+        assert!(!Diagonals.allows(&grid, size, (3, 3), 0));
+        assert!(Diagonals.allows(&grid, size, (2, 1), 0));
+
+        // (1, 2) is on the diagonal from the top right to the bottom left
+        grid[1][2] = Some(2);
+
+        assert!(!Diagonals.allows(&grid, size, (3, 0), 2));
+        assert_eq!(Diagonals.candidates(&grid, size, (0, 3)), 0b1011);
+    }
+
+    #[test]
+    fn test_diagonals() {
+        let size = Size::new(4).expect("4 is a size");
+
+        let diagonals = Diagonals.units(size);
+
+        assert_eq!(diagonals[0].cells, vec![(0, 0), (1, 1), (2, 2), (3, 3)]);
+        assert_eq!(diagonals[1].cells, vec![(0, 3), (1, 2), (2, 1), (3, 0)]);
+        assert!(diagonals.iter().all(|unit| unit.kind == Kind::Other));
+    }
+}
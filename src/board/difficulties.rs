@@ -1,7 +1,9 @@
-use std::fmt::Display;
+use std::{fmt::Display, ops::RangeInclusive};
+
+use crate::solver::Technique;
 
 /// Used to change the difficulty of a given puzzle
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Difficulties {
     Easy,
     Medium,
@@ -25,18 +27,51 @@ impl Difficulties {
         }
     }
 
-    /// Used to calculate number of empty squares
+    /// Used to get from difficulty to number, the opposite of from_num
+    pub fn to_num(&self) -> u8 {
+        match self {
+            Difficulties::Easy => 0,
+            Difficulties::Medium => 1,
+            Difficulties::Hard => 2
+        }
+    }
+
+    /// Used to calculate the number of empty squares, at most three times the positions divided by
+    /// the value
+    ///
+    /// Easy = 15 of the 81 positions of the classic board
+    /// Medium = 60 of the 81
+    /// Hard = 60 of the 81
     ///
-    /// Easy = 15 empty
-    /// Medium = 30 empty
-    /// Hard = 60 empty
+    /// Medium and Hard empty as many as they can, and are told apart by the techniques needed
     pub fn value(&self) -> usize {
         match self {
             Difficulties::Easy => 16,
-            Difficulties::Medium => 8,
+            Difficulties::Medium => 4,
             Difficulties::Hard => 4
         }
     }
+
+    /// Used to get the techniques the hardest step of a puzzle should be within
+    ///
+    /// Easy = only singles
+    /// Medium = up to pairs, triples and the pointing and box/line reductions
+    /// Hard = fish, wings and more, but still solvable without guessing
+    pub fn techniques(&self) -> RangeInclusive<Technique> {
+        match self {
+            Difficulties::Easy => Technique::HiddenSingle..=Technique::NakedSingle,
+            Difficulties::Medium => Technique::PointingPair..=Technique::HiddenTriple,
+            Difficulties::Hard => Technique::XWing..=Technique::SimpleColouring
+        }
+    }
+
+    /// Used to get the difficulty of a puzzle from the hardest technique it needs
+    pub fn from_technique(technique: Technique) -> Self {
+        [Difficulties::Easy, Difficulties::Medium, Difficulties::Hard]
+            .into_iter()
+            .find(|difficulty| difficulty.techniques().contains(&technique))
+            .unwrap_or(Difficulties::Hard)
+    }
 }
 
 impl Display for Difficulties {
@@ -0,0 +1,261 @@
+use std::path::Path;
+
+use super::{parse_error::ParseError, Board};
+
+/// The file formats puzzles are shared in
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Format {
+    /// The common one-line format, the digits 1-9 and then the letters A-G with '0' or '.' for a
+    /// blank
+    Standard,
+    /// SadMan Software Sudoku, a line of digits for each row, with '#' for metadata lines
+    Sdk,
+    /// Simple Sudoku, a grid of digits with '|' between the squares and lines of '-' between them
+    SimpleSudoku,
+}
+
+/// A puzzle read from a file, with the comment and metadata lines around it
+pub struct Puzzle {
+    pub board: Board,
+    /// The lines starting with '#', kept as they are
+    pub comments: Vec<String>,
+}
+
+impl Format {
+    /// Used to get the format from the extension of a file
+    ///
+    /// ## Arguments
+    ///
+    /// * path - The path of the file
+    ///
+    /// ## Returns
+    ///
+    /// the format, or None if the extension is not one of the formats
+    pub fn from_path(path: &str) -> Option<Self> {
+        let extension = Path::new(path).extension()?.to_str()?.to_lowercase();
+        match extension.as_str() {
+            "txt" => Some(Format::Standard),
+            "sdk" => Some(Format::Sdk),
+            "ss" => Some(Format::SimpleSudoku),
+            _ => None,
+        }
+    }
+
+    /// Gets the extension of files in the format
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Format::Standard => "txt",
+            Format::Sdk => "sdk",
+            Format::SimpleSudoku => "ss",
+        }
+    }
+
+    /// Used to read a puzzle, where every number is preset
+    ///
+    /// Lines starting with '#' are comments, and '|', '-', '+', '*' and spaces only draw the
+    /// grid. Blanks are '.', '0' or 'X'
+    ///
+    /// ## Arguments
+    ///
+    /// * string - The contents of the file
+    ///
+    /// ## Returns
+    ///
+    /// the puzzle, or the first position of the grid which could not be read
+    pub fn read(&self, string: &str) -> Result<Puzzle, ParseError> {
+        let mut comments = vec![];
+        let mut grid = String::new();
+        for line in string.lines() {
+            if line.starts_with('#') {
+                comments.push(line.to_string());
+                continue;
+            }
+            grid.extend(line.chars().filter_map(|character| match character {
+                '|' | '-' | '+' | '*' => None,
+                character if character.is_whitespace() => None,
+                'X' | 'x' => Some('.'),
+                character => Some(character),
+            }));
+        }
+        Ok(Puzzle {
+            board: Board::parse_standard(&grid)?,
+            comments,
+        })
+    }
+
+    /// Used to write a puzzle, where the numbers entered are not a part of it
+    ///
+    /// ## Arguments
+    ///
+    /// * board - The board with the puzzle to write
+    /// * comments - The comment lines to write before the puzzle
+    ///
+    /// ## Returns
+    ///
+    /// the contents of the file
+    pub fn write(&self, board: &Board, comments: &[String]) -> String {
+        let size = board.size;
+        // The number of squares next to each other
+        let across = size.side() / size.square_columns();
+        let standard = board.to_standard().chars().collect::<Vec<char>>();
+        let rows = standard.chunks(size.side()).map(|row| match self {
+            Format::Standard | Format::Sdk => row.iter().collect::<String>(),
+            Format::SimpleSudoku => {
+                let squares = row
+                    .chunks(size.square_columns())
+                    .map(|square| square.iter().collect::<String>())
+                    .collect::<Vec<String>>();
+                format!("|{}|", squares.join("|"))
+            }
+        });
+
+        let mut lines = comments.to_vec();
+        match self {
+            Format::Standard => lines.push(rows.collect()),
+            Format::Sdk => lines.extend(rows),
+            Format::SimpleSudoku => {
+                let border = format!("*{}*", "-".repeat(size.side() + across - 1));
+                let between = format!(
+                    "|{}|",
+                    vec!["-".repeat(size.square_columns()); across].join("+")
+                );
+                lines.push(border.clone());
+                for (index, row) in rows.enumerate() {
+                    if index > 0 && index % size.square_rows() == 0 {
+                        lines.push(between.clone());
+                    }
+                    lines.push(row);
+                }
+                lines.push(border);
+            }
+        }
+        lines.push(String::new());
+        lines.join("\n")
+    }
+}
+
+#[cfg(test)]
+mod formats_test {
+    use super::*;
+
+    const PUZZLE: &str =
+        "53..7....6..195....98....6.8...6...34..8.3..17...2...6.6....28....419..5....8..79";
+
+    const SDK: &str = "#A Someone
+#D A classic
+53..7....
+6..195...
+.98....6.
+8...6...3
+4..8.3..1
+7...2...6
+.6....28.
+...419..5
+....8..79
+";
+
+    const SS: &str = "*-----------*
+|53.|.7.|...|
+|6..|195|...|
+|.98|...|.6.|
+|---+---+---|
+|8..|.6.|..3|
+|4..|8.3|..1|
+|7..|.2.|..6|
+|---+---+---|
+|.6.|...|28.|
+|...|419|..5|
+|...|.8.|.79|
+*-----------*
+";
+
+    /// A board with squares two rows tall and three columns wide
+    const SMALL: &str = "*-------*
+|1..|.4.|
+|...|..6|
+|---+---|
+|..3|...|
+|5..|...|
+|---+---|
+|...|2..|
+|.1.|...|
+*-------*
+";
+
+    #[test]
+    fn test_from_path() {
+        assert_eq!(Format::from_path("puzzles/hard.sdk"), Some(Format::Sdk));
+        assert_eq!(Format::from_path("hard.SS"), Some(Format::SimpleSudoku));
+        assert_eq!(Format::from_path("save-hard"), None);
+        assert_eq!(Format::from_path("hard.txt"), Some(Format::Standard));
+        assert_eq!(Format::from_path("hard"), None);
+    }
+
+    #[test]
+    fn test_standard() {
+        let puzzle = Format::Standard
+            .read(&format!("{}\n", PUZZLE))
+            .expect("The puzzle is valid");
+
+        assert_eq!(puzzle.board.to_standard(), PUZZLE);
+        assert_eq!(
+            Format::Standard.write(&puzzle.board, &[]),
+            format!("{}\n", PUZZLE)
+        );
+
+        // Letters of either case are the numbers above nine, never preset numbers of a save
+        let large = "a".repeat(256);
+        let puzzle = Format::Standard.read(&large).expect("The puzzle is valid");
+
+        assert_eq!(puzzle.board.to_standard(), "A".repeat(256));
+    }
+
+    #[test]
+    fn test_sdk() {
+        let puzzle = Format::Sdk.read(SDK).expect("The puzzle is valid");
+
+        assert_eq!(puzzle.board.to_standard(), PUZZLE);
+        assert_eq!(puzzle.comments, vec!["#A Someone", "#D A classic"]);
+        assert_eq!(Format::Sdk.write(&puzzle.board, &puzzle.comments), SDK);
+    }
+
+    #[test]
+    fn test_simple_sudoku() {
+        let puzzle = Format::SimpleSudoku.read(SS).expect("The puzzle is valid");
+
+        assert_eq!(puzzle.board.to_standard(), PUZZLE);
+        assert!(puzzle.comments.is_empty());
+        assert_eq!(Format::SimpleSudoku.write(&puzzle.board, &[]), SS);
+
+        let puzzle = Format::SimpleSudoku
+            .read(&SS.replace('.', "X"))
+            .expect("The puzzle is valid");
+
+        assert_eq!(puzzle.board.to_standard(), PUZZLE);
+
+        let puzzle = Format::SimpleSudoku
+            .read(SMALL)
+            .expect("The puzzle is valid");
+
+        assert_eq!(
+            puzzle.board.to_standard(),
+            "1...4......6..3...5........2...1...."
+        );
+        assert_eq!(Format::SimpleSudoku.write(&puzzle.board, &[]), SMALL);
+    }
+
+    #[test]
+    fn test_read_error() {
+        assert_eq!(
+            Format::Sdk.read(&SDK.replacen('3', "?", 1)).err(),
+            Some(ParseError::InvalidCharacter {
+                position: 1,
+                character: '?'
+            })
+        );
+        assert_eq!(
+            Format::SimpleSudoku.read("|53.|.7.|...|").err(),
+            Some(ParseError::TooShort { position: 9 })
+        );
+    }
+}
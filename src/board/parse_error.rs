@@ -0,0 +1,55 @@
+use std::fmt::Display;
+
+/// The reasons a string can not be read as a board, or as a game saved with one
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParseError {
+    /// A character which is not a digit, a letter for a preset number or a '.'
+    InvalidCharacter { position: usize, character: char },
+    /// A character after the end of the board
+    TooLong { position: usize, character: char },
+    /// The string ends before the board does, at the position given
+    TooShort { position: usize },
+    /// A move of the history which can not be read, or does not fit the board
+    InvalidMove,
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseError::InvalidCharacter {
+                position,
+                character,
+            } => {
+                write!(
+                    f,
+                    "invalid character '{}' at position {}",
+                    character, position
+                )
+            }
+            ParseError::TooLong {
+                position,
+                character,
+            } => {
+                write!(
+                    f,
+                    "unexpected character '{}' after the board at position {}",
+                    character, position
+                )
+            }
+            ParseError::TooShort { position } => {
+                write!(f, "the board ends too early at position {}", position)
+            }
+            ParseError::InvalidMove => {
+                write!(f, "a move of the history does not fit the board")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+impl From<ParseError> for std::io::Error {
+    fn from(error: ParseError) -> Self {
+        std::io::Error::new(std::io::ErrorKind::InvalidData, error)
+    }
+}
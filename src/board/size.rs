@@ -0,0 +1,227 @@
+use std::{fmt::Display, str::FromStr};
+
+/// The number of positions along each side of the boards which can be played
+pub const SIDES: [usize; 5] = [4, 6, 9, 12, 16];
+
+/// The shape of a board and of its squares
+///
+/// Sides which are not a square number have rectangular squares, wider than they are tall
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Size {
+    /// The number of rows in each square
+    square_rows: usize,
+    /// The number of columns in each square
+    square_columns: usize,
+}
+
+impl Size {
+    /// Gets the size of a board with a number of positions along each side
+    ///
+    /// ## Arguments
+    ///
+    /// * side - The number of positions along each side
+    ///
+    /// ## Returns
+    ///
+    /// the size, or None if the side is not one of SIDES
+    pub fn new(side: usize) -> Option<Self> {
+        let (square_rows, square_columns) = match side {
+            4 => (2, 2),
+            6 => (2, 3),
+            9 => (3, 3),
+            12 => (3, 4),
+            16 => (4, 4),
+            _ => return None,
+        };
+        Some(Self {
+            square_rows,
+            square_columns,
+        })
+    }
+
+    /// Gets the size of a board with a number of positions in total
+    ///
+    /// ## Returns
+    ///
+    /// the size, or None if no size has that many positions
+    pub fn from_cells(cells: usize) -> Option<Self> {
+        SIDES
+            .into_iter()
+            .find(|side| side * side == cells)
+            .and_then(Self::new)
+    }
+
+    /// Gets the number of positions along each side, which is also the number of values
+    pub fn side(&self) -> usize {
+        self.square_rows * self.square_columns
+    }
+
+    /// Gets the number of positions on the board
+    pub fn cells(&self) -> usize {
+        self.side() * self.side()
+    }
+
+    /// Gets the most positions a generated puzzle leaves empty
+    ///
+    /// Past this the larger boards get slow to check for a single solution, and can rarely be
+    /// solved without guessing
+    pub fn max_empty(&self) -> usize {
+        match self.side() {
+            12 => 90,
+            16 => 140,
+            _ => self.cells(),
+        }
+    }
+
+    /// Gets the number of rows in each square
+    pub fn square_rows(&self) -> usize {
+        self.square_rows
+    }
+
+    /// Gets the number of columns in each square
+    pub fn square_columns(&self) -> usize {
+        self.square_columns
+    }
+
+    /// Gets every value, as bitflags
+    pub fn all(&self) -> u16 {
+        ((1u32 << self.side()) - 1) as u16
+    }
+
+    /// Gets the square based on the x and y
+    ///
+    /// The squares are counted row by row, from the top left
+    ///
+    /// ## Arguments
+    ///
+    /// * x - is the coordinate on the x-axis
+    /// * y - is the coordinate on the y-axis
+    pub fn get_square(&self, x: usize, y: usize) -> usize {
+        // There is a square across for every row in a square
+        (y / self.square_rows) * self.square_rows + x / self.square_columns
+    }
+
+    /// Method used to get the indexes for the two arrays
+    ///
+    /// ## Arguments
+    ///
+    /// * x - is the global x position
+    /// * y - is the global y position
+    ///
+    /// ## Returns
+    /// a tuple of (square, position inside of the square)
+    #[cfg(test)]
+    pub fn get_index(&self, x: usize, y: usize) -> (usize, usize) {
+        let first_array = self.get_square(x, y);
+        let x = x % self.square_columns;
+        let y = y % self.square_rows;
+        let second_array = (y * self.square_columns) + x;
+        (first_array, second_array)
+    }
+
+    /// Gets the (row, column) of a position inside of a square, the opposite of get_index
+    ///
+    /// ## Arguments
+    ///
+    /// * square - The square, counted row by row
+    /// * position - The position inside of the square, counted row by row
+    pub fn square_position(&self, square: usize, position: usize) -> (usize, usize) {
+        (
+            (square / self.square_rows) * self.square_rows + position / self.square_columns,
+            (square % self.square_rows) * self.square_columns + position % self.square_columns,
+        )
+    }
+}
+
+impl Default for Size {
+    /// The classic board of 9x9
+    fn default() -> Self {
+        Self {
+            square_rows: 3,
+            square_columns: 3,
+        }
+    }
+}
+
+impl Display for Size {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}x{}", self.side(), self.side())
+    }
+}
+
+impl FromStr for Size {
+    type Err = String;
+
+    /// Used to read a size from the number of positions along each side
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        string
+            .parse::<usize>()
+            .ok()
+            .and_then(Self::new)
+            .ok_or_else(|| {
+                let sides = SIDES.map(|side| side.to_string());
+                format!("the size must be one of {}", sides.join(", "))
+            })
+    }
+}
+
+#[cfg(test)]
+mod size_test {
+    use super::*;
+
+    #[test]
+    fn test_new() {
+        let size = Size::new(6).expect("6 is a size");
+
+        assert_eq!(size.side(), 6);
+        assert_eq!(size.square_rows(), 2);
+        assert_eq!(size.square_columns(), 3);
+        assert_eq!(size.all(), 0b111111);
+        assert_eq!(Size::new(16).map(|size| size.all()), Some(u16::MAX));
+        assert_eq!(Size::from_cells(144), Size::new(12));
+        assert_eq!(Size::default(), Size::new(9).expect("9 is a size"));
+        assert_eq!(Size::default().max_empty(), 81);
+        assert_eq!(Size::new(12).map(|size| size.max_empty()), Some(90));
+        assert_eq!(Size::new(16).map(|size| size.max_empty()), Some(140));
+        assert!(Size::new(8).is_none());
+        assert!(Size::from_cells(80).is_none());
+    }
+
+    #[test]
+    fn test_squares() {
+        let size = Size::default();
+
+        assert_eq!(size.get_square(3, 5), 4);
+        assert_eq!(size.get_index(6, 8), (8, 6));
+        assert_eq!(size.square_position(4, 6), (5, 3));
+
+        // Two squares across and three down, each two rows tall and three columns wide
+        let size = Size::new(6).expect("6 is a size");
+
+        assert_eq!(size.get_square(2, 1), 0);
+        assert_eq!(size.get_square(3, 1), 1);
+        assert_eq!(size.get_square(0, 2), 2);
+        assert_eq!(size.get_index(4, 5), (5, 4));
+        assert_eq!(size.square_position(5, 4), (5, 4));
+
+        for square in 0..12 {
+            for position in 0..12 {
+                let size = Size::new(12).expect("12 is a size");
+                let (row, column) = size.square_position(square, position);
+
+                assert_eq!(size.get_index(column, row), (square, position));
+            }
+        }
+    }
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!("12".parse::<Size>(), Ok(Size::new(12).expect("12 is a size")));
+        assert_eq!(
+            "7".parse::<Size>(),
+            Err(String::from("the size must be one of 4, 6, 9, 12, 16"))
+        );
+        assert!("nine".parse::<Size>().is_err());
+        assert_eq!(Size::default().to_string(), "9x9");
+    }
+}
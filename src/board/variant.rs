@@ -0,0 +1,92 @@
+use std::{fmt::Display, rc::Rc, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+
+use super::constraint::{self, Constraint, Diagonals};
+
+/// The rules a board can be played by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Variant {
+    /// Every row, column and square holds every number
+    #[default]
+    Classic,
+    /// Both diagonals between the corners also hold every number
+    X,
+}
+
+/// Every variant, in the order they are listed
+pub const VARIANTS: [Variant; 2] = [Variant::Classic, Variant::X];
+
+impl Variant {
+    /// Gets the rules of the variant, the classic ones first
+    pub fn constraints(&self) -> Vec<Rc<dyn Constraint>> {
+        let mut constraints = constraint::classic();
+        if *self == Variant::X {
+            constraints.push(Rc::new(Diagonals));
+        }
+        constraints
+    }
+
+    /// Tests if the diagonals of the board have to hold every number
+    pub fn has_diagonals(&self) -> bool {
+        *self == Variant::X
+    }
+
+    /// Gets the name of the variant, as it is shown to the player
+    pub fn name(&self) -> &'static str {
+        match self {
+            Variant::Classic => "Classic",
+            Variant::X => "Sudoku-X",
+        }
+    }
+}
+
+impl Display for Variant {
+    /// Used to write the variant as it is given to --variant
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Variant::Classic => write!(f, "classic"),
+            Variant::X => write!(f, "x"),
+        }
+    }
+}
+
+impl FromStr for Variant {
+    type Err = String;
+
+    /// Used to read a variant from the name written by Display, in either case
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        VARIANTS
+            .into_iter()
+            .find(|variant| variant.to_string().eq_ignore_ascii_case(string))
+            .ok_or_else(|| {
+                let variants = VARIANTS.map(|variant| variant.to_string());
+                format!("the variant must be one of {}", variants.join(", "))
+            })
+    }
+}
+
+#[cfg(test)]
+mod variant_test {
+    use super::*;
+
+    #[test]
+    fn test_constraints() {
+        assert_eq!(Variant::Classic.constraints().len(), 3);
+        assert_eq!(Variant::X.constraints().len(), 4);
+        assert!(Variant::X.has_diagonals());
+        assert!(!Variant::default().has_diagonals());
+    }
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!("x".parse::<Variant>(), Ok(Variant::X));
+        assert_eq!("Classic".parse::<Variant>(), Ok(Variant::Classic));
+        assert_eq!(
+            "killer".parse::<Variant>(),
+            Err(String::from("the variant must be one of classic, x"))
+        );
+        assert_eq!(Variant::X.to_string(), "x");
+    }
+}
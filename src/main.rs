@@ -1,11 +1,12 @@
 mod board;
+mod solver;
 mod app;
 mod term;
 
 use clap::Parser;
 use term::Term;
 use app::{App, run_app};
-use board::difficulties::Difficulties;
+use board::{difficulties::Difficulties, size::Size, variant::Variant};
 
 #[derive(Parser, Debug)]
 #[clap(author, version, about, long_about = None)]
@@ -14,7 +15,23 @@ struct Args {
     difficulty: u8,
 
     #[clap(short, long, value_parser, default_value = "")]
-    file: String
+    file: String,
+
+    /// Starts a new game created from the seed
+    #[clap(short, long, value_parser)]
+    seed: Option<u64>,
+
+    /// Starts the daily puzzle, the same for everyone on the same date
+    #[clap(long, value_parser)]
+    daily: bool,
+
+    /// The size of new boards: 4, 6, 9, 12 or 16 positions across
+    #[clap(long, value_parser, default_value = "9")]
+    size: Size,
+
+    /// The rules of new boards: classic, or x for both diagonals to also hold every number
+    #[clap(long, value_parser, default_value = "classic")]
+    variant: Variant
 }
 
 #[deny(clippy::pedantic)]
@@ -22,19 +39,25 @@ fn main() {
     let args = Args::parse();
     let diff = Difficulties::from_num(args.difficulty);
 
-    let file = if args.file == "" {
+    let file = if args.file.is_empty() {
         None
     } else {
         Some(args.file)
     };
 
-    let app = App::new(diff, file);
+    let app = match App::new(diff, args.size, args.variant, file, args.seed, args.daily) {
+        Ok(app) => app,
+        Err(err) => {
+            println!("Could not load the game: {err}");
+            return;
+        }
+    };
     let mut terminal = Term::new();
 
     // create app and run it
     let res = run_app(&mut terminal, app);
 
     if let Err(err) = res {
-        println!("{:?}", err);
+        println!("{err:?}");
     }
 }
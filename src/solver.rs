@@ -0,0 +1,464 @@
+pub mod rating;
+pub mod techniques;
+
+use std::fmt::Display;
+
+use crate::board::{
+    constraint::{self, Kind, Unit},
+    size::Size,
+    Grid,
+};
+
+/// The named techniques the solver can apply, ordered from easiest to hardest
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Technique {
+    HiddenSingle,
+    NakedSingle,
+    PointingPair,
+    BoxLineReduction,
+    NakedPair,
+    HiddenPair,
+    NakedTriple,
+    HiddenTriple,
+    XWing,
+    NakedQuad,
+    HiddenQuad,
+    Swordfish,
+    XYWing,
+    SimpleColouring,
+}
+
+impl Technique {
+    /// Used to get how much the technique adds to the score of a puzzle
+    pub fn score(&self) -> usize {
+        match self {
+            Technique::HiddenSingle => 1,
+            Technique::NakedSingle => 2,
+            Technique::PointingPair => 10,
+            Technique::BoxLineReduction => 10,
+            Technique::NakedPair => 20,
+            Technique::HiddenPair => 25,
+            Technique::NakedTriple => 30,
+            Technique::HiddenTriple => 35,
+            Technique::XWing => 50,
+            Technique::NakedQuad => 50,
+            Technique::HiddenQuad => 60,
+            Technique::Swordfish => 80,
+            Technique::XYWing => 80,
+            Technique::SimpleColouring => 90,
+        }
+    }
+}
+
+impl Display for Technique {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Technique::HiddenSingle => "Hidden Single",
+            Technique::NakedSingle => "Naked Single",
+            Technique::PointingPair => "Pointing Pair",
+            Technique::BoxLineReduction => "Box/Line Reduction",
+            Technique::NakedPair => "Naked Pair",
+            Technique::HiddenPair => "Hidden Pair",
+            Technique::NakedTriple => "Naked Triple",
+            Technique::HiddenTriple => "Hidden Triple",
+            Technique::XWing => "X-Wing",
+            Technique::NakedQuad => "Naked Quad",
+            Technique::HiddenQuad => "Hidden Quad",
+            Technique::Swordfish => "Swordfish",
+            Technique::XYWing => "XY-Wing",
+            Technique::SimpleColouring => "Simple Colouring",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// A single logical step taken by the solver
+#[derive(Debug, Clone, PartialEq)]
+pub struct Step {
+    /// The technique used
+    pub technique: Technique,
+    /// The positions (row, column) the technique is based on
+    pub cells: Vec<(usize, usize)>,
+    /// The value placed by the step, as (row, column, value)
+    pub placement: Option<(usize, usize, usize)>,
+    /// The candidates removed by the step, as (row, column, value)
+    pub eliminations: Vec<(usize, usize, usize)>,
+}
+
+impl Step {
+    /// Creates a step which places a value
+    pub fn placement(technique: Technique, row: usize, column: usize, value: usize) -> Self {
+        Self {
+            technique,
+            cells: vec![(row, column)],
+            placement: Some((row, column, value)),
+            eliminations: vec![],
+        }
+    }
+
+    /// Creates a step which only removes candidates
+    pub fn elimination(
+        technique: Technique,
+        cells: Vec<(usize, usize)>,
+        eliminations: Vec<(usize, usize, usize)>,
+    ) -> Self {
+        Self {
+            technique,
+            cells,
+            placement: None,
+            eliminations,
+        }
+    }
+}
+
+/// Gets the name of a position, as r1c1 for the top left
+fn cell_name((row, column): (usize, usize)) -> String {
+    format!("r{}c{}", row + 1, column + 1)
+}
+
+/// Gets the names of a list of positions
+fn cell_names(cells: &[(usize, usize)]) -> String {
+    cells
+        .iter()
+        .map(|cell| cell_name(*cell))
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+/// Gets the name of the row, column, diagonal or square holding all of the positions
+///
+/// The positions are a whole unit, so the size of the board is the number of them
+fn unit_name(cells: &[(usize, usize)]) -> String {
+    let (row, column) = cells[0];
+    if cells.iter().all(|cell| cell.0 == row) {
+        format!("row {}", row + 1)
+    } else if cells.iter().all(|cell| cell.1 == column) {
+        format!("column {}", column + 1)
+    } else if cells.iter().all(|cell| cell.0 == cell.1) {
+        String::from("the diagonal from the top left")
+    } else if cells.iter().all(|cell| cell.0 + cell.1 == cells.len() - 1) {
+        String::from("the diagonal from the top right")
+    } else {
+        let size = Size::new(cells.len()).unwrap_or_default();
+        format!("square {}", size.get_square(column, row) + 1)
+    }
+}
+
+impl Display for Step {
+    /// Used to explain the reasoning of the step
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some((row, column, value)) = self.placement {
+            let reason = match self.technique {
+                Technique::HiddenSingle => {
+                    format!("it is the only place for it in {}", unit_name(&self.cells))
+                }
+                _ => String::from("it is the only candidate left"),
+            };
+            return write!(
+                f,
+                "{}: {} goes in {}, as {}",
+                self.technique,
+                value + 1,
+                cell_name((row, column)),
+                reason
+            );
+        }
+
+        let mut values = self
+            .eliminations
+            .iter()
+            .map(|(_, _, value)| *value)
+            .collect::<Vec<usize>>();
+        values.sort();
+        values.dedup();
+        let removed = values
+            .iter()
+            .map(|value| {
+                let cells = self
+                    .eliminations
+                    .iter()
+                    .filter(|(_, _, other)| other == value)
+                    .map(|(row, column, _)| (*row, *column))
+                    .collect::<Vec<(usize, usize)>>();
+                format!("{} from {}", value + 1, cell_names(&cells))
+            })
+            .collect::<Vec<String>>()
+            .join(", ");
+        write!(
+            f,
+            "{} on {} removes {}",
+            self.technique,
+            cell_names(&self.cells),
+            removed
+        )
+    }
+}
+
+/// Used to solve a board step by step, the way a person would
+#[derive(Clone)]
+pub struct Solver {
+    /// The size of the board
+    size: Size,
+    /// The units of the rules of the board, the rows, columns and squares first
+    units: Vec<Unit>,
+    /// The units each position is in, indexed by [row][column]
+    positions: Vec<Vec<Vec<usize>>>,
+    /// The values of the board, indexed by [row][column]
+    values: Grid,
+    /// The candidates of each empty position, as bitflags
+    candidates: Vec<Vec<u16>>,
+}
+
+/// The techniques to try, in the order they are tried
+const TECHNIQUES: [fn(&Solver) -> Option<Step>; 14] = [
+    techniques::hidden_single,
+    techniques::naked_single,
+    techniques::pointing,
+    techniques::box_line_reduction,
+    techniques::naked_pair,
+    techniques::hidden_pair,
+    techniques::naked_triple,
+    techniques::hidden_triple,
+    techniques::x_wing,
+    techniques::naked_quad,
+    techniques::hidden_quad,
+    techniques::swordfish,
+    techniques::xy_wing,
+    techniques::simple_colouring,
+];
+
+impl Solver {
+    /// Creates a solver from a grid, with the candidates of each empty position filled in
+    ///
+    /// ## Arguments
+    ///
+    /// * grid - The values to start from, indexed by [row][column]
+    /// * size - The size of the grid
+    /// * units - The units of the rules of the board, as given by Board::units
+    pub fn new(grid: &Grid, size: Size, units: Vec<Unit>) -> Self {
+        let side = size.side();
+        let mut solver = Self {
+            size,
+            positions: constraint::units_of_positions(size, &units),
+            units,
+            values: vec![vec![None; side]; side],
+            candidates: vec![vec![size.all(); side]; side],
+        };
+        for (row, each) in grid.iter().enumerate() {
+            for (column, value) in each.iter().enumerate() {
+                if let Some(value) = value {
+                    solver.place(row, column, *value);
+                }
+            }
+        }
+        solver
+    }
+
+    /// Gets the size of the board
+    pub fn size(&self) -> Size {
+        self.size
+    }
+
+    /// Gets every unit of the board, the rows first, then the columns, the squares and last the
+    /// units of any other rules
+    pub fn units(&self) -> Vec<Unit> {
+        self.units.clone()
+    }
+
+    /// Gets the unit of a kind a position is in, such as its row or its square
+    ///
+    /// ## Returns
+    ///
+    /// the unit, or None if the rules of the board have no unit of the kind there
+    pub fn unit_of(&self, (row, column): (usize, usize), kind: Kind) -> Option<&Unit> {
+        self.positions[row][column]
+            .iter()
+            .map(|unit| &self.units[*unit])
+            .find(|unit| unit.kind == kind)
+    }
+
+    /// Tests if two different positions share a unit
+    pub fn sees(&self, first: (usize, usize), second: (usize, usize)) -> bool {
+        let units = &self.positions[second.0][second.1];
+        first != second
+            && self.positions[first.0][first.1]
+                .iter()
+                .any(|unit| units.contains(unit))
+    }
+
+    /// Gets the value at a position
+    pub fn value(&self, row: usize, column: usize) -> Option<usize> {
+        self.values[row][column]
+    }
+
+    /// Gets the candidates of a position, as bitflags
+    pub fn candidates(&self, row: usize, column: usize) -> u16 {
+        self.candidates[row][column]
+    }
+
+    /// Tests if a value is a candidate at a position
+    pub fn has_candidate(&self, row: usize, column: usize, value: usize) -> bool {
+        self.candidates[row][column] & (1 << value) != 0
+    }
+
+    /// Gets all positions without a value
+    pub fn empty_cells(&self) -> Vec<(usize, usize)> {
+        let side = self.size.side();
+        (0..side)
+            .flat_map(|row| (0..side).map(move |column| (row, column)))
+            .filter(|(row, column)| self.values[*row][*column].is_none())
+            .collect()
+    }
+
+    /// Tests if all positions have a value
+    pub fn is_solved(&self) -> bool {
+        self.values.iter().flatten().all(|value| value.is_some())
+    }
+
+    /// Places a value, and removes it from the candidates of every position it sees
+    fn place(&mut self, row: usize, column: usize, value: usize) {
+        self.values[row][column] = Some(value);
+        for unit in self.positions[row][column].iter() {
+            for (other_row, other_column) in self.units[*unit].cells.iter() {
+                self.candidates[*other_row][*other_column] &= !(1 << value);
+            }
+        }
+        self.candidates[row][column] = 0;
+    }
+
+    /// Finds the next step, trying the easiest techniques first
+    ///
+    /// ## Returns
+    ///
+    /// the step, or None if no technique makes progress
+    pub fn next_step(&self) -> Option<Step> {
+        TECHNIQUES.iter().find_map(|technique| technique(self))
+    }
+
+    /// Applies a step to the solver
+    pub fn apply(&mut self, step: &Step) {
+        if let Some((row, column, value)) = step.placement {
+            self.place(row, column, value);
+        }
+        for (row, column, value) in step.eliminations.iter() {
+            self.candidates[*row][*column] &= !(1 << value);
+        }
+    }
+
+    /// Applies steps until the board is solved, or no technique makes progress
+    ///
+    /// ## Returns
+    ///
+    /// the steps taken, in order
+    pub fn solve(&mut self) -> Vec<Step> {
+        let mut steps = vec![];
+        while let Some(step) = self.next_step() {
+            self.apply(&step);
+            steps.push(step);
+        }
+        steps
+    }
+}
+
+#[cfg(test)]
+mod solver_test {
+    use super::*;
+    use crate::board::{variant::Variant, Board};
+
+    /// Gets a solver for a puzzle which only needs singles
+    fn get_easy_solver() -> Solver {
+        let test_string =
+            "ec..g....f..aie....ih....f.h...f...cd..h.c..ag...b...f.f....bh....dai..e....h..gi";
+        let board = Board::parse(test_string).expect("The board is valid");
+        Solver::new(&board.empty, board.size, board.units())
+    }
+
+    #[test]
+    fn test_candidates() {
+        let solver = get_easy_solver();
+
+        // (0, 2) sees 3, 5, 6, 7, 8 and 9, leaving 1, 2 and 4
+        assert_eq!(solver.candidates(0, 2), 0b000001011);
+        assert_eq!(solver.candidates(0, 0), 0);
+        assert!(solver.has_candidate(0, 2, 0));
+        assert!(!solver.has_candidate(0, 2, 2));
+    }
+
+    #[test]
+    fn test_solve_singles() {
+        let mut solver = get_easy_solver();
+
+        let steps = solver.solve();
+
+        assert!(solver.is_solved());
+        assert_eq!(steps.len(), 51);
+        assert!(steps
+            .iter()
+            .all(|step| step.technique <= Technique::NakedSingle));
+    }
+
+    #[test]
+    fn test_units() {
+        let solver = get_easy_solver();
+        let units = solver.units();
+
+        assert_eq!(units.len(), 3 * 9);
+        assert_eq!(units[0].cells[8], (0, 8));
+        assert_eq!(units[9].cells[8], (8, 0));
+        assert_eq!(units[2 * 9 + 4].cells[0], (3, 3));
+        assert_eq!(solver.unit_of((4, 5), Kind::Square), Some(&units[2 * 9 + 4]));
+        assert_eq!(solver.unit_of((4, 5), Kind::Other), None);
+        assert!(solver.sees((0, 0), (2, 2)));
+        assert!(!solver.sees((0, 0), (3, 3)));
+        assert!(!solver.sees((0, 0), (0, 0)));
+
+        // Squares two rows tall and three columns wide
+        let units = Board::new_empty(Size::new(6).expect("6 is a size")).units();
+
+        assert_eq!(units.len(), 3 * 6);
+        assert_eq!(units[2 * 6 + 1].cells[3], (1, 3));
+        assert_eq!(units[2 * 6 + 2].cells[0], (2, 0));
+    }
+
+    #[test]
+    fn test_explain() {
+        let mut step = Step::placement(Technique::HiddenSingle, 0, 4, 2);
+        step.cells = Board::new_empty(Size::default()).units()[9 + 4].cells.clone();
+
+        assert_eq!(
+            step.to_string(),
+            "Hidden Single: 3 goes in r1c5, as it is the only place for it in column 5"
+        );
+
+        let step = Step::elimination(
+            Technique::NakedPair,
+            vec![(0, 0), (0, 1)],
+            vec![(0, 5, 1), (0, 5, 2), (0, 8, 1)],
+        );
+
+        assert_eq!(
+            step.to_string(),
+            "Naked Pair on r1c1 r1c2 removes 2 from r1c6 r1c9, 3 from r1c6"
+        );
+
+        let mut step = Step::placement(Technique::HiddenSingle, 2, 3, 0);
+        let units = Board::new_empty(Size::new(6).expect("6 is a size")).units();
+        step.cells = units[2 * 6 + 3].cells.clone();
+
+        assert_eq!(
+            step.to_string(),
+            "Hidden Single: 1 goes in r3c4, as it is the only place for it in square 4"
+        );
+
+        let mut board = Board::new_empty(Size::default());
+        board.set_variant(Variant::X);
+        let mut step = Step::placement(Technique::HiddenSingle, 2, 6, 4);
+        step.cells = board.units()[3 * 9 + 1].cells.clone();
+
+        assert_eq!(
+            step.to_string(),
+            "Hidden Single: 5 goes in r3c7, as it is the only place for it in the diagonal from \
+             the top right"
+        );
+    }
+}
@@ -0,0 +1,79 @@
+use crate::board::{difficulties::Difficulties, Board};
+
+use super::{Solver, Technique};
+
+/// Added to the score of a puzzle which can not be solved with the known techniques
+const UNSOLVED_SCORE: usize = 1000;
+
+/// How hard a puzzle is for a person to solve
+#[derive(Debug, Clone, PartialEq)]
+pub struct Rating {
+    /// The hardest technique needed, None if the puzzle is already solved
+    pub hardest: Option<Technique>,
+    /// The sum of the scores of every step needed
+    pub score: usize,
+    /// If the known techniques are enough to solve the puzzle
+    pub solved: bool,
+}
+
+impl Rating {
+    /// Rates the preset values of a board, by solving it the way a person would
+    ///
+    /// ## Arguments
+    ///
+    /// * board - The board to rate
+    pub fn new(board: &Board) -> Self {
+        let mut solver = Solver::new(&board.empty, board.size, board.units());
+        let steps = solver.solve();
+        let solved = solver.is_solved();
+
+        let hardest = steps.iter().map(|step| step.technique).max();
+        let mut score = steps.iter().map(|step| step.technique.score()).sum();
+        if !solved {
+            score += UNSOLVED_SCORE;
+        }
+
+        Self {
+            hardest,
+            score,
+            solved,
+        }
+    }
+
+    /// Gets the difficulty of the puzzle, from the hardest technique it needs
+    ///
+    /// ## Returns
+    ///
+    /// the difficulty, or None if the known techniques are not enough to solve the puzzle
+    pub fn difficulty(&self) -> Option<Difficulties> {
+        if !self.solved {
+            return None;
+        }
+        Some(self.hardest.map_or(Difficulties::Easy, Difficulties::from_technique))
+    }
+}
+
+#[cfg(test)]
+mod rating_test {
+    use super::*;
+    use crate::board::size::Size;
+
+    #[test]
+    fn test_rating() {
+        let test_string =
+            "ec..g....f..aie....ih....f.h...f...cd..h.c..ag...b...f.f....bh....dai..e....h..gi";
+        let rating = Rating::new(&Board::parse(test_string).expect("The board is valid"));
+
+        assert!(rating.solved);
+        assert!(rating.hardest <= Some(Technique::NakedSingle));
+        assert!(rating.score >= 51);
+        assert!(rating.score <= 2 * 51);
+        assert_eq!(rating.difficulty(), Some(Difficulties::Easy));
+
+        let rating = Rating::new(&Board::new_empty(Size::default()));
+
+        assert!(!rating.solved);
+        assert!(rating.score >= UNSOLVED_SCORE);
+        assert_eq!(rating.difficulty(), None);
+    }
+}
@@ -0,0 +1,807 @@
+use super::{Solver, Step, Technique};
+use crate::board::constraint::Kind;
+
+/// Gets all ways to pick a number of indexes out of a length, in increasing order
+///
+/// ## Arguments
+///
+/// * len - The number of indexes to pick from
+/// * size - The number of indexes to pick
+fn combinations(len: usize, size: usize) -> Vec<Vec<usize>> {
+    if size == 0 {
+        return vec![vec![]];
+    }
+    if len < size {
+        return vec![];
+    }
+    // Either the last index is picked, or it is not
+    let mut with_last = combinations(len - 1, size - 1);
+    for each in with_last.iter_mut() {
+        each.push(len - 1);
+    }
+    let mut without_last = combinations(len - 1, size);
+    without_last.extend(with_last);
+    without_last
+}
+
+/// Gets the values set in bitflags
+fn values_of(flags: u16) -> impl Iterator<Item = usize> {
+    (0..u16::BITS as usize).filter(move |value| flags & (1 << value) != 0)
+}
+
+/// Maps a (line, cross) to a (row, column)
+type ToCell = fn(usize, usize) -> (usize, usize);
+
+/// A position with only one candidate left
+pub fn naked_single(solver: &Solver) -> Option<Step> {
+    solver.empty_cells().into_iter().find_map(|(row, column)| {
+        let candidates = solver.candidates(row, column);
+        if candidates.count_ones() == 1 {
+            let value = candidates.trailing_zeros() as usize;
+            Some(Step::placement(Technique::NakedSingle, row, column, value))
+        } else {
+            None
+        }
+    })
+}
+
+/// A value which only has one position left in a row, column or square
+pub fn hidden_single(solver: &Solver) -> Option<Step> {
+    let side = solver.size().side();
+    for unit in solver.units() {
+        for value in 0..side {
+            let mut positions = unit
+                .cells
+                .iter()
+                .filter(|(row, column)| solver.has_candidate(*row, *column, value));
+            if let (Some((row, column)), None) = (positions.next(), positions.next()) {
+                let mut step = Step::placement(Technique::HiddenSingle, *row, *column, value);
+                // The whole unit is needed to see that there is no other place for the value
+                step.cells = unit.cells.clone();
+                return Some(step);
+            }
+        }
+    }
+    None
+}
+
+/// A number of positions in a unit which together only have the same number of candidates.
+/// Those candidates can be removed from the rest of the unit
+///
+/// ## Arguments
+///
+/// * solver - The solver to find the step in
+/// * size - The number of positions
+/// * technique - The technique to report the step as
+fn naked_subset(solver: &Solver, size: usize, technique: Technique) -> Option<Step> {
+    for unit in solver.units() {
+        let empty = unit
+            .cells
+            .into_iter()
+            .filter(|(row, column)| solver.value(*row, *column).is_none())
+            .collect::<Vec<(usize, usize)>>();
+
+        for picked in combinations(empty.len(), size) {
+            let union = picked.iter().fold(0, |union, index| {
+                let (row, column) = empty[*index];
+                union | solver.candidates(row, column)
+            });
+            if union.count_ones() as usize != size {
+                continue;
+            }
+
+            let eliminations = empty
+                .iter()
+                .enumerate()
+                .filter(|(index, _)| !picked.contains(index))
+                .flat_map(|(_, (row, column))| {
+                    values_of(solver.candidates(*row, *column) & union)
+                        .map(move |value| (*row, *column, value))
+                })
+                .collect::<Vec<(usize, usize, usize)>>();
+            if !eliminations.is_empty() {
+                let cells = picked.iter().map(|index| empty[*index]).collect();
+                return Some(Step::elimination(technique, cells, eliminations));
+            }
+        }
+    }
+    None
+}
+
+/// A number of values in a unit which together only have the same number of positions.
+/// All other candidates can be removed from those positions
+///
+/// ## Arguments
+///
+/// * solver - The solver to find the step in
+/// * size - The number of values
+/// * technique - The technique to report the step as
+fn hidden_subset(solver: &Solver, size: usize, technique: Technique) -> Option<Step> {
+    let side = solver.size().side();
+    for unit in solver.units() {
+        // The positions of each value in the unit, as bitflags of the index in the unit
+        let positions = (0..side)
+            .map(|value| {
+                unit.cells
+                    .iter()
+                    .enumerate()
+                    .fold(0u16, |flags, (index, (row, column))| {
+                        if solver.has_candidate(*row, *column, value) {
+                            flags | (1 << index)
+                        } else {
+                            flags
+                        }
+                    })
+            })
+            .collect::<Vec<u16>>();
+        let values = (0..side)
+            .filter(|value| (2..=size).contains(&(positions[*value].count_ones() as usize)))
+            .collect::<Vec<usize>>();
+
+        for picked in combinations(values.len(), size) {
+            let union = picked
+                .iter()
+                .fold(0, |union, index| union | positions[values[*index]]);
+            if union.count_ones() as usize != size {
+                continue;
+            }
+
+            let kept = picked
+                .iter()
+                .fold(0u16, |kept, index| kept | (1 << values[*index]));
+            let cells = values_of(union)
+                .map(|index| unit.cells[index])
+                .collect::<Vec<(usize, usize)>>();
+            let eliminations = cells
+                .iter()
+                .flat_map(|(row, column)| {
+                    values_of(solver.candidates(*row, *column) & !kept)
+                        .map(move |value| (*row, *column, value))
+                })
+                .collect::<Vec<(usize, usize, usize)>>();
+            if !eliminations.is_empty() {
+                return Some(Step::elimination(technique, cells, eliminations));
+            }
+        }
+    }
+    None
+}
+
+/// Two positions in a unit which only have the same two candidates
+pub fn naked_pair(solver: &Solver) -> Option<Step> {
+    naked_subset(solver, 2, Technique::NakedPair)
+}
+
+/// Two values which only have the same two positions in a unit
+pub fn hidden_pair(solver: &Solver) -> Option<Step> {
+    hidden_subset(solver, 2, Technique::HiddenPair)
+}
+
+/// Three positions in a unit which together only have the same three candidates
+pub fn naked_triple(solver: &Solver) -> Option<Step> {
+    naked_subset(solver, 3, Technique::NakedTriple)
+}
+
+/// Three values which together only have the same three positions in a unit
+pub fn hidden_triple(solver: &Solver) -> Option<Step> {
+    hidden_subset(solver, 3, Technique::HiddenTriple)
+}
+
+/// Four positions in a unit which together only have the same four candidates
+pub fn naked_quad(solver: &Solver) -> Option<Step> {
+    naked_subset(solver, 4, Technique::NakedQuad)
+}
+
+/// Four values which together only have the same four positions in a unit
+pub fn hidden_quad(solver: &Solver) -> Option<Step> {
+    hidden_subset(solver, 4, Technique::HiddenQuad)
+}
+
+/// A value whose positions in a square all lie in one row or column.
+/// The value can be removed from the rest of that row or column
+pub fn pointing(solver: &Solver) -> Option<Step> {
+    let units = solver.units();
+    let side = solver.size().side();
+    for square in units.iter().filter(|unit| unit.kind == Kind::Square) {
+        for value in 0..side {
+            let cells = square
+                .cells
+                .iter()
+                .filter(|(row, column)| solver.has_candidate(*row, *column, value))
+                .copied()
+                .collect::<Vec<(usize, usize)>>();
+            if cells.len() < 2 {
+                continue;
+            }
+
+            let (first_row, first_column) = cells[0];
+            let kind = if cells.iter().all(|(row, _)| *row == first_row) {
+                Kind::Row
+            } else if cells.iter().all(|(_, column)| *column == first_column) {
+                Kind::Column
+            } else {
+                continue;
+            };
+            let Some(line) = solver.unit_of(cells[0], kind) else {
+                continue;
+            };
+
+            let eliminations = line
+                .cells
+                .iter()
+                .filter(|(row, column)| {
+                    !square.cells.contains(&(*row, *column))
+                        && solver.has_candidate(*row, *column, value)
+                })
+                .map(|(row, column)| (*row, *column, value))
+                .collect::<Vec<(usize, usize, usize)>>();
+            if !eliminations.is_empty() {
+                return Some(Step::elimination(
+                    Technique::PointingPair,
+                    cells,
+                    eliminations,
+                ));
+            }
+        }
+    }
+    None
+}
+
+/// A value whose positions in a row or column all lie in one square.
+/// The value can be removed from the rest of that square
+pub fn box_line_reduction(solver: &Solver) -> Option<Step> {
+    let side = solver.size().side();
+    let units = solver.units();
+    for line in units
+        .iter()
+        .filter(|unit| matches!(unit.kind, Kind::Row | Kind::Column))
+    {
+        for value in 0..side {
+            let cells = line
+                .cells
+                .iter()
+                .filter(|(row, column)| solver.has_candidate(*row, *column, value))
+                .copied()
+                .collect::<Vec<(usize, usize)>>();
+            if cells.len() < 2 {
+                continue;
+            }
+
+            let Some(square) = solver.unit_of(cells[0], Kind::Square) else {
+                continue;
+            };
+            if !cells.iter().all(|cell| square.cells.contains(cell)) {
+                continue;
+            }
+
+            let eliminations = square
+                .cells
+                .iter()
+                .filter(|(row, column)| {
+                    !line.cells.contains(&(*row, *column))
+                        && solver.has_candidate(*row, *column, value)
+                })
+                .map(|(row, column)| (*row, *column, value))
+                .collect::<Vec<(usize, usize, usize)>>();
+            if !eliminations.is_empty() {
+                return Some(Step::elimination(
+                    Technique::BoxLineReduction,
+                    cells,
+                    eliminations,
+                ));
+            }
+        }
+    }
+    None
+}
+
+/// A value which in a number of rows only has positions in the same number of columns.
+/// The value can be removed from the rest of those columns. The same goes for columns and rows
+///
+/// ## Arguments
+///
+/// * solver - The solver to find the step in
+/// * size - The number of rows or columns
+/// * technique - The technique to report the step as
+fn fish(solver: &Solver, size: usize, technique: Technique) -> Option<Step> {
+    // First for rows and then for columns
+    let orientations: [ToCell; 2] = [|line, cross| (line, cross), |line, cross| (cross, line)];
+    let side = solver.size().side();
+
+    for value in 0..side {
+        for to_cell in orientations {
+            // The crossing positions of the value in each line, as bitflags
+            let lines = (0..side)
+                .map(|line| {
+                    (0..side).fold(0u16, |flags, cross| {
+                        let (row, column) = to_cell(line, cross);
+                        if solver.has_candidate(row, column, value) {
+                            flags | (1 << cross)
+                        } else {
+                            flags
+                        }
+                    })
+                })
+                .collect::<Vec<u16>>();
+            let base = (0..side)
+                .filter(|line| (2..=size).contains(&(lines[*line].count_ones() as usize)))
+                .collect::<Vec<usize>>();
+
+            for picked in combinations(base.len(), size) {
+                let picked = picked
+                    .iter()
+                    .map(|index| base[*index])
+                    .collect::<Vec<usize>>();
+                let union = picked.iter().fold(0, |union, line| union | lines[*line]);
+                if union.count_ones() as usize != size {
+                    continue;
+                }
+
+                let eliminations = (0..side)
+                    .filter(|line| !picked.contains(line))
+                    .flat_map(|line| values_of(union).map(move |cross| to_cell(line, cross)))
+                    .filter(|(row, column)| solver.has_candidate(*row, *column, value))
+                    .map(|(row, column)| (row, column, value))
+                    .collect::<Vec<(usize, usize, usize)>>();
+                if !eliminations.is_empty() {
+                    let cells = picked
+                        .iter()
+                        .flat_map(|line| {
+                            values_of(lines[*line]).map(move |cross| to_cell(*line, cross))
+                        })
+                        .collect();
+                    return Some(Step::elimination(technique, cells, eliminations));
+                }
+            }
+        }
+    }
+    None
+}
+
+/// A value which in two rows only has positions in the same two columns, or the other way around
+pub fn x_wing(solver: &Solver) -> Option<Step> {
+    fish(solver, 2, Technique::XWing)
+}
+
+/// A value which in three rows only has positions in the same three columns, or the other way
+/// around
+pub fn swordfish(solver: &Solver) -> Option<Step> {
+    fish(solver, 3, Technique::Swordfish)
+}
+
+/// A pivot with the candidates xy, which sees two pincers with the candidates xz and yz.
+/// Whichever value the pivot takes, one of the pincers is z, so z can be removed from every
+/// position seeing both pincers
+pub fn xy_wing(solver: &Solver) -> Option<Step> {
+    let empty = solver.empty_cells();
+    let pairs = empty
+        .iter()
+        .filter(|(row, column)| solver.candidates(*row, *column).count_ones() == 2)
+        .copied()
+        .collect::<Vec<(usize, usize)>>();
+    let candidates = |(row, column): (usize, usize)| solver.candidates(row, column);
+
+    for pivot in pairs.iter() {
+        for first in pairs.iter() {
+            let shared = candidates(*first) & candidates(*pivot);
+            if !solver.sees(*pivot, *first) || shared.count_ones() != 1 {
+                continue;
+            }
+            let z = candidates(*first) & !candidates(*pivot);
+            let wanted = (candidates(*pivot) & !shared) | z;
+
+            for second in pairs.iter() {
+                if second == first || !solver.sees(*pivot, *second) || candidates(*second) != wanted {
+                    continue;
+                }
+                let value = z.trailing_zeros() as usize;
+
+                let eliminations = empty
+                    .iter()
+                    .filter(|cell| {
+                        *cell != pivot
+                            && solver.sees(**cell, *first)
+                            && solver.sees(**cell, *second)
+                            && solver.has_candidate(cell.0, cell.1, value)
+                    })
+                    .map(|(row, column)| (*row, *column, value))
+                    .collect::<Vec<(usize, usize, usize)>>();
+                if !eliminations.is_empty() {
+                    return Some(Step::elimination(
+                        Technique::XYWing,
+                        vec![*pivot, *first, *second],
+                        eliminations,
+                    ));
+                }
+            }
+        }
+    }
+    None
+}
+
+/// Colours chains of positions where a value is one of only two in a unit with two alternating
+/// colours. Exactly one of the colours holds the value. If two positions of the same colour see
+/// each other, that colour is wrong. A position seeing both colours can not hold the value
+pub fn simple_colouring(solver: &Solver) -> Option<Step> {
+    let units = solver.units();
+    let side = solver.size().side();
+    let empty = solver.empty_cells();
+
+    for value in 0..side {
+        // Pairs of positions which are the only two for the value in a unit
+        let links = units
+            .iter()
+            .filter_map(|unit| {
+                let cells = unit
+                    .cells
+                    .iter()
+                    .filter(|(row, column)| solver.has_candidate(*row, *column, value))
+                    .copied()
+                    .collect::<Vec<(usize, usize)>>();
+                if cells.len() == 2 {
+                    Some((cells[0], cells[1]))
+                } else {
+                    None
+                }
+            })
+            .collect::<Vec<((usize, usize), (usize, usize))>>();
+
+        let mut coloured = vec![];
+        for (start, _) in links.iter() {
+            if coloured.contains(start) {
+                continue;
+            }
+
+            let mut colours = [vec![*start], vec![]];
+            let mut queue = vec![(*start, 0)];
+            while let Some((cell, colour)) = queue.pop() {
+                for (first, second) in links.iter() {
+                    let other = if *first == cell {
+                        *second
+                    } else if *second == cell {
+                        *first
+                    } else {
+                        continue;
+                    };
+                    if !colours[0].contains(&other) && !colours[1].contains(&other) {
+                        colours[1 - colour].push(other);
+                        queue.push((other, 1 - colour));
+                    }
+                }
+            }
+            coloured.extend(colours.iter().flatten().copied());
+            let cells = colours.concat();
+
+            for colour in colours.iter() {
+                let wrapped = colour
+                    .iter()
+                    .any(|first| colour.iter().any(|second| solver.sees(*first, *second)));
+                if wrapped {
+                    let eliminations = colour
+                        .iter()
+                        .map(|(row, column)| (*row, *column, value))
+                        .collect();
+                    return Some(Step::elimination(
+                        Technique::SimpleColouring,
+                        cells,
+                        eliminations,
+                    ));
+                }
+            }
+
+            let eliminations = empty
+                .iter()
+                .filter(|(row, column)| {
+                    !cells.contains(&(*row, *column)) && solver.has_candidate(*row, *column, value)
+                })
+                .filter(|cell| {
+                    colours
+                        .iter()
+                        .all(|colour| colour.iter().any(|other| solver.sees(**cell, *other)))
+                })
+                .map(|(row, column)| (*row, *column, value))
+                .collect::<Vec<(usize, usize, usize)>>();
+            if !eliminations.is_empty() {
+                return Some(Step::elimination(
+                    Technique::SimpleColouring,
+                    cells,
+                    eliminations,
+                ));
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod techniques_test {
+    use super::*;
+    use crate::board::{constraint, size::Size};
+
+    /// The size of the boards the techniques are tested on
+    const SIDE: usize = 9;
+
+    /// Gets a solver for a board given as a string of 81 digits, with 0 for an empty position
+    fn get_solver(digits: &str) -> Solver {
+        let mut grid = vec![vec![None; SIDE]; SIDE];
+        for (pos, each) in digits.chars().enumerate() {
+            let value = each.to_digit(10).unwrap() as usize;
+            if value > 0 {
+                grid[pos / SIDE][pos % SIDE] = Some(value - 1);
+            }
+        }
+        let size = Size::default();
+        Solver::new(&grid, size, constraint::units(size, &constraint::classic()))
+    }
+
+    /// Gets a solver with the candidates of the given positions limited to only the given values
+    fn limit(mut solver: Solver, limits: &[((usize, usize), &[usize])]) -> Solver {
+        for ((row, column), values) in limits {
+            let kept = values
+                .iter()
+                .fold(0u16, |kept, value| kept | (1 << (value - 1)));
+            let eliminations = values_of(solver.candidates(*row, *column) & !kept)
+                .map(|value| (*row, *column, value))
+                .collect();
+            solver.apply(&Step::elimination(
+                Technique::NakedPair,
+                vec![],
+                eliminations,
+            ));
+        }
+        solver
+    }
+
+    #[test]
+    fn test_combinations() {
+        assert_eq!(combinations(3, 2), vec![vec![0, 1], vec![0, 2], vec![1, 2]]);
+        assert_eq!(combinations(4, 3).len(), 4);
+        assert!(combinations(1, 2).is_empty());
+    }
+
+    #[test]
+    fn test_naked_single() {
+        let solver = get_solver(
+            "123456780000000000000000000000000000000000000000000000000000000000000000000000000",
+        );
+
+        let step = naked_single(&solver).unwrap();
+
+        assert_eq!(step.placement, Some((0, 8, 8)));
+        assert_eq!(step.technique, Technique::NakedSingle);
+    }
+
+    #[test]
+    fn test_hidden_single() {
+        // 1 is seen in every position of the first square except (0, 0)
+        let solver = get_solver(
+            "000000000000100000000000100000000000010000000000000000000000000001000000000000000",
+        );
+
+        let step = hidden_single(&solver).unwrap();
+
+        assert_eq!(step.placement, Some((0, 0, 0)));
+        assert_eq!(step.cells, solver.units()[0].cells);
+    }
+
+    #[test]
+    fn test_naked_pair() {
+        let solver = limit(
+            get_solver(&"0".repeat(81)),
+            &[((0, 0), &[1, 2]), ((0, 1), &[1, 2])],
+        );
+
+        let step = naked_pair(&solver).unwrap();
+
+        assert_eq!(step.cells, vec![(0, 0), (0, 1)]);
+        assert!(step.eliminations.contains(&(0, 8, 0)));
+        assert!(step.eliminations.contains(&(0, 5, 1)));
+        assert!(!step.eliminations.iter().any(|(_, _, value)| *value > 1));
+    }
+
+    #[test]
+    fn test_hidden_pair() {
+        // 1 and 2 can only be at (0, 0) and (0, 1) in the first row
+        let limits = (2..SIDE)
+            .map(|column| ((0, column), &[3, 4, 5, 6, 7, 8, 9][..]))
+            .collect::<Vec<((usize, usize), &[usize])>>();
+        let solver = limit(get_solver(&"0".repeat(81)), &limits);
+
+        let step = hidden_pair(&solver).unwrap();
+
+        assert_eq!(step.cells, vec![(0, 0), (0, 1)]);
+        assert!(step.eliminations.contains(&(0, 0, 2)));
+        assert!(!step.eliminations.iter().any(|(_, _, value)| *value < 2));
+    }
+
+    #[test]
+    fn test_pointing() {
+        // 1 can only be in the first row of the first square
+        let limits = [(1, 0), (1, 1), (1, 2), (2, 0), (2, 1), (2, 2)]
+            .into_iter()
+            .map(|cell| (cell, &[2, 3, 4, 5, 6, 7, 8, 9][..]))
+            .collect::<Vec<((usize, usize), &[usize])>>();
+        let solver = limit(get_solver(&"0".repeat(81)), &limits);
+
+        let step = pointing(&solver).unwrap();
+
+        assert_eq!(step.technique, Technique::PointingPair);
+        assert_eq!(step.eliminations.len(), 6);
+        assert!(step.eliminations.contains(&(0, 8, 0)));
+    }
+
+    #[test]
+    fn test_box_line_reduction() {
+        // 1 can only be in the first square of the first row
+        let limits = (3..SIDE)
+            .map(|column| ((0, column), &[2, 3, 4, 5, 6, 7, 8, 9][..]))
+            .collect::<Vec<((usize, usize), &[usize])>>();
+        let solver = limit(get_solver(&"0".repeat(81)), &limits);
+
+        let step = box_line_reduction(&solver).unwrap();
+
+        assert_eq!(step.cells, vec![(0, 0), (0, 1), (0, 2)]);
+        assert_eq!(step.eliminations.len(), 6);
+        assert!(step.eliminations.contains(&(2, 2, 0)));
+    }
+
+    #[test]
+    fn test_unit_order() {
+        // The rows, columns and squares are found by their kind, in whatever order they come
+        let size = Size::default();
+        let grid = vec![vec![None; SIDE]; SIDE];
+        let mut units = constraint::units(size, &constraint::classic());
+        units.reverse();
+        let solver = Solver::new(&grid, size, units);
+
+        let limits = (3..SIDE)
+            .map(|column| ((0, column), &[2, 3, 4, 5, 6, 7, 8, 9][..]))
+            .collect::<Vec<((usize, usize), &[usize])>>();
+        let step = box_line_reduction(&limit(solver.clone(), &limits)).unwrap();
+
+        assert_eq!(step.cells, vec![(0, 0), (0, 1), (0, 2)]);
+        assert!(step.eliminations.contains(&(2, 2, 0)));
+
+        let limits = [(1, 0), (1, 1), (1, 2), (2, 0), (2, 1), (2, 2)]
+            .into_iter()
+            .map(|cell| (cell, &[2, 3, 4, 5, 6, 7, 8, 9][..]))
+            .collect::<Vec<((usize, usize), &[usize])>>();
+        let step = pointing(&limit(solver, &limits)).unwrap();
+
+        assert_eq!(step.cells, vec![(0, 0), (0, 1), (0, 2)]);
+        assert!(step.eliminations.contains(&(0, 8, 0)));
+    }
+
+    #[test]
+    fn test_x_wing() {
+        // 1 can only be in column 0 and 4 in both row 0 and row 4
+        let no_one = &[2, 3, 4, 5, 6, 7, 8, 9][..];
+        let limits = [0, 4]
+            .into_iter()
+            .flat_map(|row| [1, 2, 3, 5, 6, 7, 8].map(|column| ((row, column), no_one)))
+            .collect::<Vec<((usize, usize), &[usize])>>();
+        let solver = limit(get_solver(&"0".repeat(81)), &limits);
+
+        let step = x_wing(&solver).unwrap();
+
+        assert_eq!(step.cells, vec![(0, 0), (0, 4), (4, 0), (4, 4)]);
+        assert!(step.eliminations.contains(&(8, 0, 0)));
+        assert!(step.eliminations.contains(&(1, 4, 0)));
+        assert!(!step.eliminations.contains(&(4, 4, 0)));
+    }
+
+    #[test]
+    fn test_naked_triple() {
+        let solver = limit(
+            get_solver(&"0".repeat(81)),
+            &[((0, 0), &[1, 2]), ((0, 1), &[2, 3]), ((0, 2), &[1, 3])],
+        );
+
+        assert!(naked_pair(&solver).is_none());
+        let step = naked_triple(&solver).unwrap();
+
+        assert_eq!(step.cells, vec![(0, 0), (0, 1), (0, 2)]);
+        assert_eq!(step.eliminations.len(), 18);
+        assert!(!step.eliminations.iter().any(|(_, _, value)| *value > 2));
+    }
+
+    #[test]
+    fn test_hidden_triple() {
+        // 1, 2 and 3 can only be in the first three positions of the first row
+        let limits = (3..SIDE)
+            .map(|column| ((0, column), &[4, 5, 6, 7, 8, 9][..]))
+            .collect::<Vec<((usize, usize), &[usize])>>();
+        let solver = limit(get_solver(&"0".repeat(81)), &limits);
+
+        let step = hidden_triple(&solver).unwrap();
+
+        assert_eq!(step.cells, vec![(0, 0), (0, 1), (0, 2)]);
+        assert_eq!(step.eliminations.len(), 18);
+        assert!(!step.eliminations.iter().any(|(_, _, value)| *value < 3));
+    }
+
+    #[test]
+    fn test_naked_quad() {
+        let solver = limit(
+            get_solver(&"0".repeat(81)),
+            &[
+                ((0, 0), &[1, 2]),
+                ((0, 1), &[2, 3]),
+                ((0, 2), &[3, 4]),
+                ((0, 3), &[1, 4]),
+            ],
+        );
+
+        assert!(naked_triple(&solver).is_none());
+        let step = naked_quad(&solver).unwrap();
+
+        assert_eq!(step.cells, vec![(0, 0), (0, 1), (0, 2), (0, 3)]);
+        assert_eq!(step.eliminations.len(), 20);
+    }
+
+    #[test]
+    fn test_hidden_quad() {
+        // 1, 2, 3 and 4 can only be in the first four positions of the first row
+        let limits = (4..SIDE)
+            .map(|column| ((0, column), &[5, 6, 7, 8, 9][..]))
+            .collect::<Vec<((usize, usize), &[usize])>>();
+        let solver = limit(get_solver(&"0".repeat(81)), &limits);
+
+        let step = hidden_quad(&solver).unwrap();
+
+        assert_eq!(step.cells, vec![(0, 0), (0, 1), (0, 2), (0, 3)]);
+        assert_eq!(step.eliminations.len(), 20);
+    }
+
+    #[test]
+    fn test_swordfish() {
+        // 1 can only be in column 0, 3 and 6 in row 0, 3 and 6
+        let no_one = &[2, 3, 4, 5, 6, 7, 8, 9][..];
+        let limits = [0, 3, 6]
+            .into_iter()
+            .flat_map(|row| [1, 2, 4, 5, 7, 8].map(|column| ((row, column), no_one)))
+            .collect::<Vec<((usize, usize), &[usize])>>();
+        let solver = limit(get_solver(&"0".repeat(81)), &limits);
+
+        assert!(x_wing(&solver).is_none());
+        let step = swordfish(&solver).unwrap();
+
+        assert_eq!(step.cells.len(), 9);
+        assert_eq!(step.eliminations.len(), 18);
+        assert!(step.eliminations.contains(&(1, 0, 0)));
+        assert!(!step.eliminations.contains(&(3, 3, 0)));
+    }
+
+    #[test]
+    fn test_xy_wing() {
+        let solver = limit(
+            get_solver(&"0".repeat(81)),
+            &[((0, 0), &[1, 2]), ((0, 4), &[1, 3]), ((4, 0), &[2, 3])],
+        );
+
+        let step = xy_wing(&solver).unwrap();
+
+        assert_eq!(step.cells, vec![(0, 0), (0, 4), (4, 0)]);
+        assert_eq!(step.eliminations, vec![(4, 4, 2)]);
+    }
+
+    #[test]
+    fn test_simple_colouring() {
+        // 1 is linked from (0, 0) to (1, 2) in the first square, to (1, 6) in the second row and
+        // to (5, 6) in the seventh column. (5, 0) sees both (0, 0) and (5, 6), which have
+        // different colours
+        let with_one = [(0, 0), (1, 2), (1, 6), (5, 6), (5, 0), (5, 3), (7, 0)];
+        let no_one = &[2, 3, 4, 5, 6, 7, 8, 9][..];
+        let limits = (0..SIDE)
+            .flat_map(|row| (0..SIDE).map(move |column| (row, column)))
+            .filter(|cell| !with_one.contains(cell))
+            .map(|cell| (cell, no_one))
+            .collect::<Vec<((usize, usize), &[usize])>>();
+        let solver = limit(get_solver(&"0".repeat(81)), &limits);
+
+        let step = simple_colouring(&solver).unwrap();
+
+        assert_eq!(step.cells.len(), 4);
+        assert_eq!(step.eliminations, vec![(5, 0, 0)]);
+    }
+}
@@ -1,18 +1,33 @@
 use std::io::{stdout, Stdout};
 
 use crossterm::{
+    cursor::Show,
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
 use tui::{backend::CrosstermBackend, Terminal, Frame};
 
+/// Gives the terminal back as it was before the start, ignoring errors as nothing more can be done
+fn restore() {
+    let _ = disable_raw_mode();
+    let _ = execute!(stdout(), LeaveAlternateScreen, Show);
+}
+
 pub struct Term {
     pub terminal: Terminal<CrosstermBackend<Stdout>>,
 }
 
 impl Term {
     /// On startup creates and sets everything needed to control the terminal as needed
+    ///
+    /// A panic gives the terminal back before the message is written, so it can be read
     pub fn new() -> Self {
+        let hook = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |info| {
+            restore();
+            hook(info);
+        }));
+
         let backend = CrosstermBackend::new(stdout());
         let term = Self {
             terminal: Terminal::new(backend).unwrap(),
@@ -40,8 +55,6 @@ impl Term {
 impl Drop for Term {
     /// Drops the terminal, so everything used to start the terminal is removed
     fn drop(&mut self) {
-        disable_raw_mode().unwrap();
-        execute!(self.terminal.backend_mut(), LeaveAlternateScreen).unwrap();
-        self.terminal.show_cursor().unwrap();
+        restore();
     }
 }